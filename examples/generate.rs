@@ -0,0 +1,52 @@
+use std::env;
+
+use anyhow::bail;
+use sudoku::{
+    board::{Board, DisplayOptions},
+    solver::{LogicalResult, Sudoku},
+};
+
+fn main() -> anyhow::Result<()> {
+    let difficulty = env::args().nth(1).unwrap_or_else(|| String::from("easy"));
+    let wants_guessing = match difficulty.as_str() {
+        "easy" => false,
+        "hard" => true,
+        other => bail!("unknown difficulty {:?} (expected easy or hard)", other),
+    };
+
+    let mut rng = rand::thread_rng();
+    let options = DisplayOptions {
+        empty_char: '.',
+        box_separators: true,
+        row_separator: true,
+    };
+
+    loop {
+        let mut solution = Board::random_valid(11, &mut rng)?;
+        let Some(puzzle) = Sudoku::new(&mut solution)
+            .solution_iter()
+            .next()
+            .and_then(|solution| Board::derive_puzzles(&solution, 1, &mut rng).pop())
+        else {
+            continue;
+        };
+
+        let mut solved = puzzle.clone();
+        let required_guessing = match Sudoku::new(&mut solved).solve_logically() {
+            LogicalResult::SolvedLogically => false,
+            LogicalResult::RequiredGuessing(_) => true,
+            LogicalResult::Unsolvable => continue,
+        };
+        if required_guessing != wants_guessing {
+            continue;
+        }
+
+        let mut unique = puzzle.clone();
+        Sudoku::new(&mut unique).solve_strict()?;
+        assert!(unique.validate_complete().is_ok());
+
+        println!("Puzzle ({}):\n{}", difficulty, puzzle.format(&options));
+        println!("Solution:\n{}", unique.format(&options));
+        return Ok(());
+    }
+}
@@ -1,4 +1,6 @@
-use std::{array, collections::HashSet, fmt::Display};
+use std::{array, collections::HashSet, fmt::Display, ops::Deref, str::FromStr};
+
+use rand::{seq::SliceRandom, Rng};
 
 #[derive(Debug, thiserror::Error)]
 pub enum SudokuError {
@@ -6,18 +8,81 @@ pub enum SudokuError {
     Value(u8),
     #[error("input length must have 81 characters (was {0})")]
     InputLength(usize),
+    #[error("invalid character in input ({0:?})")]
+    InvalidCharacter(char),
+    #[error("region {region} must have 9 cells (was {size})")]
+    RegionSize { region: u8, size: usize },
+    #[error("{0:?} is not completed")]
+    UnitNotCompleted(Unit),
+    #[error("band or stack index must be between 0 and 2 (was {0})")]
+    BandIndex(usize),
+    #[error("row {row} must have 9 characters (was {length})")]
+    RowLength { row: usize, length: usize },
+    #[error("region {0} is not contiguous")]
+    RegionDisconnected(u8),
+    #[error("invalid move ({0:?})")]
+    InvalidMove(String),
+    #[error("cell index is out of range (was {0})")]
+    Index(usize),
+    #[error("solution does not solve the puzzle")]
+    SolutionMismatch,
+    #[error("{value} at ({row:?}, {column:?}) conflicts with its row, column or box")]
+    Conflict { row: Row, column: Column, value: u8 },
+    #[error("value must be between 1 and 4 (was {0})")]
+    Value4(u8),
+    #[error("input length must have 16 characters (was {0})")]
+    InputLength4(usize),
+    #[error("({row}, {column}) is outside the 4x4 grid")]
+    Coordinate4 { row: usize, column: usize },
+    #[error("could not place {0} digits without a conflict")]
+    Unfillable(usize),
+    #[error("expected a puzzle and a solution (found {0} parts)")]
+    PairFormat(usize),
+    #[error("candidates of cell {cell} must have 9 characters (was {length})")]
+    CandidateLength { cell: usize, length: usize },
 }
 
-#[derive(Debug)]
-pub struct Board([[Cell; 9]; 9]);
+#[derive(Clone, Default)]
+pub struct Board([[Cell; 9]; 9], Masks);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Digits placed in each row, column and box, one bit per digit. The masks are
+/// `stale` after cells were handed out by `row_mut` or `iter_mut`.
+#[derive(Debug, Clone, Copy, Default)]
+struct Masks {
+    rows: [u16; 9],
+    columns: [u16; 9],
+    squares: [u16; 9],
+    stale: bool,
+}
+
+/// A set of the digits 1-9 packed into the bits of a `u16`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CandidateSet(u16);
+
+/// Pencil marks for every cell, kept separately from the board's values.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CandidateGrid([[CandidateSet; 9]; 9]);
+
+/// A board whose cells can no longer change; only `&self` methods of
+/// [`Board`] are reachable through it.
+///
+/// ```compile_fail
+/// use sudoku::board::{Board, Column, Row};
+///
+/// let mut frozen = Board::new().freeze();
+/// frozen.set_cell((Row::A, Column::One), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FrozenBoard(Board);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Cell {
+    #[default]
     Empty,
     Value(u8),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Row {
     A,
     B,
@@ -30,7 +95,7 @@ pub enum Row {
     I,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Column {
     One,
     Two,
@@ -43,6 +108,67 @@ pub enum Column {
     Nine,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Row(Row),
+    Column(Column),
+    Square(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct Regions([[u8; 9]; 9]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    #[default]
+    Standard,
+    XSudoku,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    AntiKnight,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cage {
+    pub cells: Vec<(Row, Column)>,
+    pub sum: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayOptions {
+    pub empty_char: char,
+    pub box_separators: bool,
+    pub row_separator: bool,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            empty_char: '_',
+            box_separators: false,
+            row_separator: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    None,
+    Rotational180,
+    Horizontal,
+    Vertical,
+    Diagonal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Order {
+    #[default]
+    RowMajor,
+    ColumnMajor,
+}
+
 impl TryFrom<usize> for Row {
     type Error = SudokuError;
 
@@ -113,6 +239,77 @@ impl From<Column> for usize {
     }
 }
 
+impl Unit {
+    pub fn all() -> impl Iterator<Item = Unit> {
+        let rows = (0..9).map(|i| Unit::Row(Row::try_from(i).unwrap()));
+        let columns = (0..9).map(|i| Unit::Column(Column::try_from(i).unwrap()));
+        let squares = (0..9).map(Unit::Square);
+        rows.chain(columns).chain(squares)
+    }
+}
+
+impl Regions {
+    pub fn new(layout: [[u8; 9]; 9]) -> Result<Self, SudokuError> {
+        let regions = Self(layout);
+        regions.validate_sizes()?;
+        Ok(regions)
+    }
+
+    pub fn validate(&self) -> Result<(), SudokuError> {
+        self.validate_sizes()?;
+        match (0..9).find(|&region| !self.is_contiguous(region)) {
+            Some(region) => Err(SudokuError::RegionDisconnected(region)),
+            None => Ok(()),
+        }
+    }
+
+    fn validate_sizes(&self) -> Result<(), SudokuError> {
+        for region in 0..9 {
+            let size = self.0.iter().flatten().filter(|&&id| id == region).count();
+            if size != 9 {
+                return Err(SudokuError::RegionSize { region, size });
+            }
+        }
+        Ok(())
+    }
+
+    fn is_contiguous(&self, region: u8) -> bool {
+        let cells: Vec<(usize, usize)> = (0..81)
+            .map(|index| (index / 9, index % 9))
+            .filter(|&(row, column)| self.0[row][column] == region)
+            .collect();
+        let Some(&start) = cells.first() else {
+            return false;
+        };
+
+        let mut seen = vec![start];
+        let mut stack = vec![start];
+        while let Some((row, column)) = stack.pop() {
+            for neighbour in cells
+                .iter()
+                .copied()
+                .filter(|&(r, c)| r.abs_diff(row) + c.abs_diff(column) == 1)
+            {
+                if !seen.contains(&neighbour) {
+                    seen.push(neighbour);
+                    stack.push(neighbour);
+                }
+            }
+        }
+        seen.len() == cells.len()
+    }
+
+    pub fn standard() -> Self {
+        Self(array::from_fn(|row| {
+            array::from_fn(|column| ((row / 3) * 3 + column / 3) as u8)
+        }))
+    }
+
+    pub fn region_of(&self, coordinates: (Row, Column)) -> u8 {
+        self.0[usize::from(coordinates.0)][usize::from(coordinates.1)]
+    }
+}
+
 impl TryFrom<u8> for Cell {
     type Error = SudokuError;
 
@@ -125,48 +322,150 @@ impl TryFrom<u8> for Cell {
     }
 }
 
-impl Default for Board {
-    fn default() -> Self {
-        Self(array::from_fn(|_| array::from_fn(|_| Cell::Empty)))
-    }
-}
-
 impl Board {
     pub fn new() -> Self {
         Self::default()
     }
 
-    fn get_cell_mut(&mut self, coordinates: (Row, Column)) -> Option<&mut Cell> {
+    pub fn get_cell(&self, coordinates: (Row, Column)) -> Option<Cell> {
         self.0
-            .get_mut(usize::from(coordinates.0))
-            .and_then(|row| row.get_mut(usize::from(coordinates.1)))
+            .get(usize::from(coordinates.0))
+            .and_then(|row| row.get(usize::from(coordinates.1)))
+            .copied()
+    }
+
+    fn from_cells(cells: [[Cell; 9]; 9]) -> Self {
+        let mut board = Self(cells, Masks::default());
+        board.refresh_masks();
+        board
+    }
+
+    fn refresh_masks(&mut self) {
+        let mut masks = Masks::default();
+        for (row, cells) in self.0.iter().enumerate() {
+            for (column, &cell) in cells.iter().enumerate() {
+                let bit = digit_bit(cell);
+                masks.rows[row] |= bit;
+                masks.columns[column] |= bit;
+                masks.squares[square_index(row, column)] |= bit;
+            }
+        }
+        self.1 = masks;
+    }
+
+    fn write(&mut self, coordinates: (Row, Column), cell: Cell) {
+        let (row, column) = (usize::from(coordinates.0), usize::from(coordinates.1));
+        let previous = std::mem::replace(&mut self.0[row][column], cell);
+        if self.1.stale {
+            self.refresh_masks();
+            return;
+        }
+        let square = square_index(row, column);
+        if previous == Cell::Empty {
+            let bit = digit_bit(cell);
+            self.1.rows[row] |= bit;
+            self.1.columns[column] |= bit;
+            self.1.squares[square] |= bit;
+        } else {
+            self.1.rows[row] = used_mask(&self.get_row(coordinates.0));
+            self.1.columns[column] = used_mask(&self.get_column(coordinates.1));
+            self.1.squares[square] = used_mask(&self.get_square_of(coordinates));
+        }
+    }
+
+    fn unit_mask(&self, unit: Unit) -> u16 {
+        match unit {
+            _ if self.1.stale => used_mask(&self.cells_of(unit)),
+            Unit::Row(row) => self.1.rows[usize::from(row)],
+            Unit::Column(column) => self.1.columns[usize::from(column)],
+            Unit::Square(index) => self.1.squares[index],
+        }
+    }
+
+    fn used_digits(&self, coordinates: (Row, Column)) -> u16 {
+        if self.1.stale {
+            return used_mask(self.get_units_of(coordinates).as_flattened());
+        }
+        let (row, column) = (usize::from(coordinates.0), usize::from(coordinates.1));
+        self.1.rows[row] | self.1.columns[column] | self.1.squares[square_index(row, column)]
+    }
+
+    pub fn freeze(self) -> FrozenBoard {
+        FrozenBoard(self)
     }
 
     pub fn set_cell(&mut self, coordinates: (Row, Column), number: u8) -> Result<(), SudokuError> {
-        self.get_cell_mut(coordinates)
-            .and_then(|c| {
-                *c = Cell::try_from(number).unwrap();
-                Some(())
+        self.set(coordinates, Cell::try_from(number)?)
+    }
+
+    pub fn set(&mut self, coordinates: (Row, Column), cell: Cell) -> Result<(), SudokuError> {
+        match cell {
+            Cell::Value(num) if !(1..=9).contains(&num) => return Err(SudokuError::Value(num)),
+            _ => {}
+        }
+        self.write(coordinates, cell);
+        Ok(())
+    }
+
+    pub fn clear_cell(&mut self, coordinates: (Row, Column)) {
+        self.write(coordinates, Cell::Empty);
+    }
+
+    pub fn set_cell_checked(
+        &mut self,
+        coordinates: (Row, Column),
+        number: u8,
+    ) -> Result<(), SudokuError> {
+        if self.forbidden(coordinates).contains(number) {
+            return Err(SudokuError::Conflict {
+                row: coordinates.0,
+                column: coordinates.1,
+                value: number,
+            });
+        }
+        self.set_cell(coordinates, number)
+    }
+
+    pub fn apply_moves_from_str(&mut self, moves: &str) -> Result<(), SudokuError> {
+        let moves = moves
+            .split(',')
+            .map(|token| {
+                let invalid = || SudokuError::InvalidMove(token.to_string());
+                let (coordinates, value) = token.trim().split_once('=').ok_or_else(invalid)?;
+                let coordinates = parse_coordinates(coordinates).ok_or_else(invalid)?;
+                let value: u8 = value.parse().map_err(|_| invalid())?;
+                Ok((coordinates, Cell::try_from(value)?))
             })
-            .ok_or_else(|| SudokuError::Value(number))
+            .collect::<Result<Vec<((Row, Column), Cell)>, SudokuError>>()?;
+
+        for (coordinates, cell) in moves {
+            self.write(coordinates, cell);
+        }
+        Ok(())
     }
 
     pub fn is_row_completed(&self, row: Row) -> bool {
-        let index = usize::from(row);
-        let mut set = HashSet::new();
-        self.0[index].iter().all(|cell: &Cell| match cell {
-            Cell::Empty => false,
-            Cell::Value(num) => set.insert(num),
-        })
+        if self.1.stale {
+            return is_completed(self.get_row(row));
+        }
+        self.1.rows[usize::from(row)] == ALL_DIGITS
     }
 
     pub fn is_column_completed(&self, col: Column) -> bool {
-        let col: usize = col.into();
-        let mut set = HashSet::new();
-        self.0.iter().map(|row| row[col]).all(|cell| match cell {
-            Cell::Empty => false,
-            Cell::Value(num) => set.insert(num),
-        })
+        if self.1.stale {
+            return is_completed(self.get_column(col));
+        }
+        self.1.columns[usize::from(col)] == ALL_DIGITS
+    }
+
+    pub fn is_square_completed(&self, square_index: usize) -> bool {
+        if square_index >= 9 {
+            return false;
+        }
+        if self.1.stale {
+            return is_completed(self.cells_of(Unit::Square(square_index)));
+        }
+        self.1.squares[square_index] == ALL_DIGITS
     }
 
     pub fn all_rows_completed(&self) -> bool {
@@ -177,162 +476,3385 @@ impl Board {
         (0..9).all(|column: usize| self.is_column_completed(column.try_into().unwrap()))
     }
 
-    pub fn get_square_of(&self, coordinate: (Row, Column)) -> [Cell; 9] {
-        todo!()
+    pub fn all_squares_completed(&self) -> bool {
+        (0..9).all(|square| self.is_square_completed(square))
     }
 
-    pub fn get_row(&self, row: Row) -> [Cell; 9] {
-        let index = usize::from(row);
-        self.0[index]
+    pub fn missing_in_row(&self, row: Row) -> CandidateSet {
+        CandidateSet(ALL_DIGITS & !self.unit_mask(Unit::Row(row)))
     }
 
-    pub fn get_column(&self, column: Column) -> [Cell; 9] {
-        let index = usize::from(column);
-        self.0
-            .iter()
-            .map(|row| row[index])
-            .collect::<Vec<Cell>>()
-            .try_into()
-            .unwrap()
+    pub fn missing_in_column(&self, column: Column) -> CandidateSet {
+        CandidateSet(ALL_DIGITS & !self.unit_mask(Unit::Column(column)))
     }
 
-    // pub fn get_units_of(&self, coordinate: (Row, Column)) -> [[Cell; 9]; 3] {
-    //     self.0
-    // }
-}
+    pub fn missing_in_square(&self, coordinate: (Row, Column)) -> CandidateSet {
+        let square = square_index(usize::from(coordinate.0), usize::from(coordinate.1));
+        CandidateSet(ALL_DIGITS & !self.unit_mask(Unit::Square(square)))
+    }
 
-impl TryFrom<&str> for Board {
-    type Error = SudokuError;
+    pub fn get_region(&self, regions: &Regions, region: u8) -> Vec<Cell> {
+        self.iter()
+            .zip(regions.0.iter().flatten())
+            .filter(|&(_, &id)| id == region)
+            .map(|(cell, _)| cell)
+            .collect()
+    }
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        if value.len() != 81 {
-            return Err(SudokuError::InputLength(value.len()));
+    pub fn is_region_completed(&self, regions: &Regions, region: u8) -> bool {
+        let cells = self.get_region(regions, region);
+        cells.len() == 9 && digit_mask(&cells) == Some(ALL_DIGITS)
+    }
+
+    pub fn is_valid_jigsaw(&self, regions: &Regions) -> bool {
+        (0..9).all(|index| {
+            no_duplicates(self.get_row(Row::try_from(index).unwrap()))
+                && no_duplicates(self.get_column(Column::try_from(index).unwrap()))
+                && digit_mask(&self.get_region(regions, index as u8)).is_some()
+        })
+    }
+
+    pub fn is_valid(&self) -> bool {
+        (0..9).all(|index| {
+            let row = Row::try_from(index).unwrap();
+            let column = Column::try_from(index).unwrap();
+            let square = (
+                Row::try_from((index / 3) * 3).unwrap(),
+                Column::try_from((index % 3) * 3).unwrap(),
+            );
+            no_duplicates(self.get_row(row))
+                && no_duplicates(self.get_column(column))
+                && no_duplicates(self.get_square_of(square))
+        })
+    }
+
+    pub fn is_valid_for(&self, variant: Variant) -> bool {
+        match variant {
+            Variant::Standard => self.is_valid(),
+            Variant::XSudoku => {
+                self.is_valid() && self.get_diagonals().into_iter().all(no_duplicates)
+            }
         }
+    }
 
-        let mut board: [[Cell; 9]; 9] = array::from_fn(|_| array::from_fn(|_| Cell::Empty));
+    pub fn is_valid_with(&self, constraints: &[Constraint]) -> bool {
+        self.is_valid()
+            && constraints.iter().all(|constraint| match constraint {
+                Constraint::AntiKnight => (0..81).all(|index| {
+                    let (row, column) = (index / 9, index % 9);
+                    match self.0[row][column] {
+                        Cell::Empty => true,
+                        value => knight_moves(row, column)
+                            .all(|(row, column)| self.0[row][column] != value),
+                    }
+                }),
+            })
+    }
 
-        value
-            .as_bytes()
-            .chunks(9)
-            .map(std::str::from_utf8)
-            .map(|row| -> [Cell; 9] {
-                row.unwrap()
-                    .chars()
-                    .map(|c| Cell::try_from(c.to_digit(10).unwrap() as u8).unwrap())
-                    .collect::<Vec<Cell>>()
-                    .try_into()
-                    .unwrap()
+    pub fn is_valid_killer(&self, cages: &[Cage]) -> bool {
+        self.is_valid()
+            && cages.iter().all(|cage| {
+                let values = self.cage_values(cage);
+                let distinct = values.iter().collect::<HashSet<_>>().len() == values.len();
+                let total: u32 = values.iter().map(|&num| num as u32).sum();
+                distinct
+                    && if values.len() == cage.cells.len() {
+                        total == cage.sum as u32
+                    } else {
+                        total < cage.sum as u32
+                    }
             })
-            .enumerate()
-            .for_each(|(i, row)| board[i] = row);
+    }
 
-        Ok(Self(board))
+    fn cage_values(&self, cage: &Cage) -> Vec<u8> {
+        cage.cells
+            .iter()
+            .filter_map(|&coordinates| match self.get_cell(coordinates) {
+                Some(Cell::Value(num)) => Some(num),
+                _ => None,
+            })
+            .collect()
     }
-}
 
-impl Display for Board {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.0 {
-            for cell in row {
-                match cell {
-                    Cell::Empty => write!(f, "_")?,
-                    Cell::Value(val) => write!(f, "{}", val)?,
-                };
+    pub fn get_diagonals(&self) -> [[Cell; 9]; 2] {
+        [
+            array::from_fn(|i| self.0[i][i]),
+            array::from_fn(|i| self.0[i][8 - i]),
+        ]
+    }
+
+    pub fn all_diagonals_completed(&self) -> bool {
+        self.get_diagonals().into_iter().all(is_completed)
+    }
+
+    pub fn cells_of(&self, unit: Unit) -> [Cell; 9] {
+        match unit {
+            Unit::Row(row) => self.get_row(row),
+            Unit::Column(column) => self.get_column(column),
+            Unit::Square(index) => self.get_square_of((
+                Row::try_from((index / 3) * 3).unwrap(),
+                Column::try_from((index % 3) * 3).unwrap(),
+            )),
+        }
+    }
+
+    pub fn assert_solution(&self, expected: &Board) -> Result<(), Vec<(Row, Column)>> {
+        let mismatches: Vec<(Row, Column)> = (0..81)
+            .map(|index| (index / 9, index % 9))
+            .filter(|&(row, column)| self.0[row][column] != expected.0[row][column])
+            .map(|(row, column)| {
+                (
+                    Row::try_from(row).unwrap(),
+                    Column::try_from(column).unwrap(),
+                )
+            })
+            .collect();
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatches)
+        }
+    }
+
+    pub fn is_valid_solution_of(&self, puzzle: &Board) -> bool {
+        self.validate_complete().is_ok()
+            && self
+                .iter()
+                .zip(puzzle.iter())
+                .all(|(cell, given)| given == Cell::Empty || cell == given)
+    }
+
+    pub fn unit_progress(&self, unit: Unit) -> f32 {
+        let filled = self
+            .cells_of(unit)
+            .iter()
+            .filter(|&&cell| cell != Cell::Empty)
+            .count();
+        filled as f32 / 9.0
+    }
+
+    pub fn duplicates_in_unit(&self, unit: Unit) -> Vec<u8> {
+        let mut counts = [0; 10];
+        for cell in self.cells_of(unit) {
+            if let Cell::Value(val) = cell {
+                counts[val as usize] += 1;
             }
-            writeln!(f)?;
         }
+        (1..=9).filter(|&val| counts[val as usize] > 1).collect()
+    }
 
-        Ok(())
+    pub fn unit_coordinates(unit: Unit) -> [(Row, Column); 9] {
+        array::from_fn(|i| {
+            let (row, column) = match unit {
+                Unit::Row(row) => (usize::from(row), i),
+                Unit::Column(column) => (i, usize::from(column)),
+                Unit::Square(index) => ((index / 3) * 3 + i / 3, (index % 3) * 3 + i % 3),
+            };
+            (
+                Row::try_from(row).unwrap(),
+                Column::try_from(column).unwrap(),
+            )
+        })
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    pub fn unit_completions(&self, unit: Unit) -> Vec<[Cell; 9]> {
+        let cells = self.cells_of(unit);
+        let empties: Vec<(usize, HashSet<u8>)> = Board::unit_coordinates(unit)
+            .into_iter()
+            .enumerate()
+            .filter(|&(i, _)| cells[i] == Cell::Empty)
+            .map(|(i, coordinates)| (i, self.candidates(coordinates)))
+            .collect();
 
-    #[test]
-    fn row_completed() {
-        let mut sudoku = Board::new();
-        sudoku.set_cell((Row::A, Column::One), 1).unwrap();
-        sudoku.set_cell((Row::A, Column::Two), 2).unwrap();
-        sudoku.set_cell((Row::A, Column::Three), 3).unwrap();
-        sudoku.set_cell((Row::A, Column::Four), 4).unwrap();
-        sudoku.set_cell((Row::A, Column::Five), 5).unwrap();
-        sudoku.set_cell((Row::A, Column::Six), 6).unwrap();
-        sudoku.set_cell((Row::A, Column::Seven), 7).unwrap();
-        sudoku.set_cell((Row::A, Column::Eight), 8).unwrap();
-        sudoku.set_cell((Row::A, Column::Nine), 9).unwrap();
-        assert!(sudoku.is_row_completed(Row::A))
+        let mut completions = Vec::new();
+        fill_unit(&mut cells.clone(), &empties, &mut completions);
+        completions
     }
 
-    #[test]
-    fn row_contains_empty_cell() {
-        let mut sudoku = Board::new();
-        sudoku.set_cell((Row::A, Column::One), 1).unwrap();
+    pub fn conflicts_by_unit(&self) -> Vec<(Unit, Vec<u8>)> {
+        Unit::all()
+            .map(|unit| (unit, self.duplicates_in_unit(unit)))
+            .filter(|(_, digits)| !digits.is_empty())
+            .collect()
+    }
 
-        assert!(!sudoku.is_row_completed(Row::A))
+    pub fn first_conflict(&self) -> Option<(Unit, u8)> {
+        Unit::all().find_map(|unit| {
+            self.duplicates_in_unit(unit)
+                .first()
+                .map(|&digit| (unit, digit))
+        })
     }
 
-    #[test]
-    fn row_contains_duplicates() {
-        let mut sudoku = Board::new();
-        sudoku.set_cell((Row::A, Column::One), 1).unwrap();
-        sudoku.set_cell((Row::A, Column::Two), 1).unwrap();
+    pub fn completion_report(&self) -> Vec<(Unit, bool)> {
+        Unit::all()
+            .map(|unit| (unit, is_completed(self.cells_of(unit))))
+            .collect()
+    }
 
-        assert!(!sudoku.is_row_completed(Row::A))
+    pub fn validate_complete(&self) -> Result<(), SudokuError> {
+        match Unit::all().find(|&unit| !is_completed(self.cells_of(unit))) {
+            Some(unit) => Err(SudokuError::UnitNotCompleted(unit)),
+            None => Ok(()),
+        }
     }
 
-    #[test]
-    fn column_completed() {
-        let mut sudoku = Board::new();
-        sudoku.set_cell((Row::A, Column::One), 1).unwrap();
-        sudoku.set_cell((Row::B, Column::One), 2).unwrap();
-        sudoku.set_cell((Row::C, Column::One), 3).unwrap();
-        sudoku.set_cell((Row::D, Column::One), 4).unwrap();
-        sudoku.set_cell((Row::E, Column::One), 5).unwrap();
-        sudoku.set_cell((Row::F, Column::One), 6).unwrap();
-        sudoku.set_cell((Row::G, Column::One), 7).unwrap();
-        sudoku.set_cell((Row::H, Column::One), 8).unwrap();
-        sudoku.set_cell((Row::I, Column::One), 9).unwrap();
-        assert!(sudoku.is_column_completed(Column::One))
+    pub fn get_square_of(&self, coordinate: (Row, Column)) -> [Cell; 9] {
+        let row = (usize::from(coordinate.0) / 3) * 3;
+        let column = (usize::from(coordinate.1) / 3) * 3;
+        array::from_fn(|i| self.0[row + i / 3][column + i % 3])
     }
 
-    #[test]
-    fn column_contains_empty_cell() {
-        let mut sudoku = Board::new();
-        sudoku.set_cell((Row::B, Column::Two), 1).unwrap();
+    pub fn get_row(&self, row: Row) -> [Cell; 9] {
+        let index = usize::from(row);
+        self.0[index]
+    }
 
-        assert!(!sudoku.is_column_completed(Column::Two))
+    pub fn band(&self, index: usize) -> Result<[[Cell; 9]; 3], SudokuError> {
+        if index > 2 {
+            return Err(SudokuError::BandIndex(index));
+        }
+        Ok(array::from_fn(|row| self.0[index * 3 + row]))
     }
 
-    #[test]
-    fn column_contains_duplicates() {
-        let mut sudoku = Board::new();
-        sudoku.set_cell((Row::A, Column::Two), 1).unwrap();
-        sudoku.set_cell((Row::B, Column::Two), 1).unwrap();
+    pub fn stack(&self, index: usize) -> Result<[[Cell; 3]; 9], SudokuError> {
+        if index > 2 {
+            return Err(SudokuError::BandIndex(index));
+        }
+        Ok(array::from_fn(|row| {
+            array::from_fn(|column| self.0[row][index * 3 + column])
+        }))
+    }
 
-        assert!(!sudoku.is_column_completed(Column::Two))
+    pub fn row_mut(&mut self, row: Row) -> &mut [Cell; 9] {
+        self.1.stale = true;
+        &mut self.0[usize::from(row)]
     }
 
-    #[test]
-    fn all_rows_and_columns_completed() {
-        let input =
-            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
-        let board = Board::try_from(input).unwrap();
+    pub fn get_column(&self, column: Column) -> [Cell; 9] {
+        let index = usize::from(column);
+        self.0
+            .iter()
+            .map(|row| row[index])
+            .collect::<Vec<Cell>>()
+            .try_into()
+            .unwrap()
+    }
 
-        assert!(board.all_rows_completed());
-        assert!(board.all_columns_completed());
+    pub fn forbidden(&self, coordinates: (Row, Column)) -> CandidateSet {
+        let (row, column) = coordinates;
+        if self.0[usize::from(row)][usize::from(column)] != Cell::Empty {
+            // The unit masks include this cell's own digit, so read the peers.
+            let peers: Vec<Cell> = peers_of(coordinates)
+                .into_iter()
+                .map(|(row, column)| self.0[usize::from(row)][usize::from(column)])
+                .collect();
+            return CandidateSet(used_mask(&peers));
+        }
+        CandidateSet(self.used_digits(coordinates))
     }
 
-    #[test]
-    fn row_not_completed() {
-        let input =
-            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
-        let mut board = Board::try_from(input).unwrap();
+    pub fn candidates(&self, coordinates: (Row, Column)) -> HashSet<u8> {
+        self.candidate_set(coordinates).iter().collect()
+    }
 
-        board.set_cell((Row::A, Column::One), 1).unwrap();
-        board.set_cell((Row::A, Column::Two), 1).unwrap();
+    pub fn candidate_grid(&self) -> CandidateGrid {
+        CandidateGrid(array::from_fn(|row| {
+            array::from_fn(|column| {
+                self.candidate_set((
+                    Row::try_from(row).unwrap(),
+                    Column::try_from(column).unwrap(),
+                ))
+            })
+        }))
+    }
 
-        assert!(!board.all_rows_completed());
+    /// Candidates after one pass of singles: naked singles are removed from
+    /// their peers and hidden singles keep only their digit. No value is placed.
+    pub fn propagated_candidates(&self) -> CandidateGrid {
+        let mut grid = self.candidate_grid();
+        let cells = || (0..81).map(|index| coordinate_of(index).unwrap());
+        let naked: Vec<((Row, Column), u8)> = cells()
+            .filter(|&coordinates| grid.get(coordinates).len() == 1)
+            .filter_map(|coordinates| Some((coordinates, grid.get(coordinates).iter().next()?)))
+            .collect();
+        let mut hidden = Vec::new();
+        for unit in Unit::all() {
+            let coordinates = Board::unit_coordinates(unit);
+            for digit in 1..=9 {
+                let mut positions = coordinates
+                    .into_iter()
+                    .filter(|&coordinates| grid.get(coordinates).contains(digit));
+                if let (Some(position), None) = (positions.next(), positions.next()) {
+                    hidden.push((position, digit));
+                }
+            }
+        }
+
+        for ((row, column), digit) in hidden {
+            grid.0[usize::from(row)][usize::from(column)] = CandidateSet::from_iter([digit]);
+        }
+        for (coordinates, digit) in naked {
+            for peer in peers_of(coordinates) {
+                grid.remove(peer, digit);
+            }
+        }
+        grid
+    }
+
+    pub fn candidate_set(&self, coordinates: (Row, Column)) -> CandidateSet {
+        let (row, column) = coordinates;
+        if self.0[usize::from(row)][usize::from(column)] != Cell::Empty {
+            return CandidateSet::default();
+        }
+        CandidateSet(ALL_DIGITS & !self.used_digits(coordinates))
+    }
+
+    pub(crate) fn variant_candidates(
+        &self,
+        coordinates: (Row, Column),
+        variant: Variant,
+    ) -> CandidateSet {
+        let mut candidates = self.candidate_set(coordinates);
+        if variant == Variant::XSudoku {
+            let (row, column) = (usize::from(coordinates.0), usize::from(coordinates.1));
+            let [main, anti] = self.get_diagonals();
+            let diagonal_cells = (row == column)
+                .then_some(main)
+                .into_iter()
+                .chain((row + column == 8).then_some(anti))
+                .flatten();
+            diagonal_cells.for_each(|cell| {
+                if let Cell::Value(num) = cell {
+                    candidates.remove(num);
+                }
+            });
+        }
+        candidates
+    }
+
+    pub(crate) fn constrained_candidates(
+        &self,
+        coordinates: (Row, Column),
+        constraints: &[Constraint],
+    ) -> CandidateSet {
+        let mut candidates = self.candidate_set(coordinates);
+        let (row, column) = (usize::from(coordinates.0), usize::from(coordinates.1));
+        constraints.iter().for_each(|constraint| match constraint {
+            Constraint::AntiKnight => knight_moves(row, column).for_each(|(row, column)| {
+                if let Cell::Value(num) = self.0[row][column] {
+                    candidates.remove(num);
+                }
+            }),
+        });
+        candidates
+    }
+
+    pub(crate) fn killer_candidates(
+        &self,
+        coordinates: (Row, Column),
+        cages: &[Cage],
+    ) -> CandidateSet {
+        let mut candidates = self.candidate_set(coordinates);
+        if candidates.is_empty() {
+            return candidates;
+        }
+
+        for cage in cages
+            .iter()
+            .filter(|cage| cage.cells.contains(&coordinates))
+        {
+            let placed = self.cage_values(cage);
+            let others = cage.cells.len() - placed.len() - 1;
+            let remaining = cage.sum as i32 - placed.iter().map(|&num| num as i32).sum::<i32>();
+
+            candidates.retain(|value| {
+                if placed.contains(&value) {
+                    return false;
+                }
+                let rest: Vec<i32> = (1..=9)
+                    .filter(|num| *num != value && !placed.contains(num))
+                    .map(i32::from)
+                    .collect();
+                if others > rest.len() {
+                    return false;
+                }
+                let min: i32 = rest[..others].iter().sum();
+                let max: i32 = rest[rest.len() - others..].iter().sum();
+                (min..=max).contains(&(remaining - value as i32))
+            });
+        }
+        candidates
+    }
+
+    pub(crate) fn region_candidates(
+        &self,
+        coordinates: (Row, Column),
+        regions: &Regions,
+    ) -> CandidateSet {
+        let (row, column) = coordinates;
+        if self.0[usize::from(row)][usize::from(column)] != Cell::Empty {
+            return CandidateSet::default();
+        }
+
+        let mut candidates = CandidateSet::all();
+        self.get_row(row)
+            .iter()
+            .chain(self.get_column(column).iter())
+            .chain(
+                self.get_region(regions, regions.region_of(coordinates))
+                    .iter(),
+            )
+            .for_each(|cell| {
+                if let Cell::Value(num) = cell {
+                    candidates.remove(*num);
+                }
+            });
+        candidates
+    }
+
+    pub fn empty_cells_by_difficulty(&self) -> Vec<((Row, Column), u8)> {
+        let mut cells: Vec<((Row, Column), u8)> = (0..81)
+            .map(|index| {
+                (
+                    Row::try_from(index / 9).unwrap(),
+                    Column::try_from(index % 9).unwrap(),
+                )
+            })
+            .filter(|&(row, column)| self.0[usize::from(row)][usize::from(column)] == Cell::Empty)
+            .map(|coordinates| (coordinates, self.candidates(coordinates).len() as u8))
+            .collect();
+        cells.sort_by_key(|&(_, count)| count);
+        cells
+    }
+
+    pub fn constraint_scores(&self) -> [[u8; 9]; 9] {
+        array::from_fn(|row| {
+            array::from_fn(|column| {
+                if self.0[row][column] != Cell::Empty {
+                    return 0;
+                }
+                (0..81)
+                    .map(|index| (index / 9, index % 9))
+                    .filter(|&(r, c)| {
+                        (r, c) != (row, column)
+                            && (r == row || c == column || (r / 3, c / 3) == (row / 3, column / 3))
+                    })
+                    .filter(|&(r, c)| self.0[r][c] != Cell::Empty)
+                    .count() as u8
+            })
+        })
+    }
+
+    pub fn digit_counts(&self) -> [u8; 9] {
+        let mut counts = [0; 9];
+        for cell in self.iter() {
+            if let Cell::Value(val) = cell {
+                counts[val as usize - 1] += 1;
+            }
+        }
+        counts
+    }
+
+    pub fn status_line(&self) -> String {
+        let conflicts: usize = self
+            .conflicts_by_unit()
+            .iter()
+            .map(|(_, digits)| digits.len())
+            .sum();
+        let empty = 81 - self.clue_count();
+        match (conflicts, empty) {
+            (0, 0) => String::from("solved"),
+            (0, empty) => format!("valid, {} empty", empty),
+            (conflicts, _) => format!("invalid: {} conflicts", conflicts),
+        }
+    }
+
+    pub fn clue_count(&self) -> usize {
+        self.iter().filter(|&cell| cell != Cell::Empty).count()
+    }
+
+    pub fn naked_singles(&self) -> Vec<((Row, Column), u8)> {
+        self.entries()
+            .filter_map(|(row, column, _)| {
+                let candidates = self.candidates((row, column));
+                match candidates.len() {
+                    1 => candidates
+                        .into_iter()
+                        .next()
+                        .map(|value| ((row, column), value)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    pub fn hidden_singles(&self) -> Vec<((Row, Column), u8)> {
+        let mut singles = Vec::new();
+        for unit in Unit::all() {
+            let coordinates = Board::unit_coordinates(unit);
+            let candidates = coordinates.map(|coordinates| self.candidates(coordinates));
+            for value in 1..=9 {
+                let mut positions = (0..9).filter(|&i| candidates[i].contains(&value));
+                if let (Some(i), None) = (positions.next(), positions.next()) {
+                    if !singles.contains(&(coordinates[i], value)) {
+                        singles.push((coordinates[i], value));
+                    }
+                }
+            }
+        }
+        singles
+    }
+
+    pub fn bivalue_cells(&self, candidates: &CandidateGrid) -> Vec<((Row, Column), (u8, u8))> {
+        self.entries()
+            .filter(|&(_, _, cell)| cell == Cell::Empty)
+            .filter_map(|(row, column, _)| {
+                let candidates: Vec<u8> = candidates.get((row, column)).iter().collect();
+                match candidates[..] {
+                    [a, b] => Some(((row, column), (a, b))),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    pub fn box_forced_rows(&self) -> Vec<(usize, u8, Row)> {
+        let mut forced = Vec::new();
+        for square in 0..9 {
+            let coordinates = Board::unit_coordinates(Unit::Square(square));
+            let candidates: Vec<((Row, Column), HashSet<u8>)> = coordinates
+                .into_iter()
+                .map(|coordinates| (coordinates, self.candidates(coordinates)))
+                .collect();
+
+            for digit in 1..=9 {
+                let mut rows = candidates
+                    .iter()
+                    .filter(|(_, values)| values.contains(&digit))
+                    .map(|&((row, _), _)| row);
+                if let Some(row) = rows.next() {
+                    if rows.all(|other| other == row) {
+                        forced.push((square, digit, row));
+                    }
+                }
+            }
+        }
+        forced
+    }
+
+    pub fn candidate_entropy(&self) -> f32 {
+        self.entries()
+            .filter(|&(_, _, cell)| cell == Cell::Empty)
+            .map(|(row, column, _)| (self.candidates((row, column)).len().max(1) as f32).log2())
+            .sum()
+    }
+
+    pub fn dead_cells(&self) -> Vec<(Row, Column)> {
+        self.empty_cells_by_difficulty()
+            .into_iter()
+            .take_while(|&(_, count)| count == 0)
+            .map(|(coordinates, _)| coordinates)
+            .collect()
+    }
+
+    pub fn try_from_moves<I>(moves: I) -> Result<Board, SudokuError>
+    where
+        I: IntoIterator<Item = ((Row, Column), u8)>,
+    {
+        let mut board = Board::new();
+        for (coordinates, number) in moves {
+            board.write(coordinates, Cell::try_from(number)?);
+        }
+        Ok(board)
+    }
+
+    pub fn from_reader<R: std::io::Read>(mut reader: R) -> anyhow::Result<Board> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents)?;
+        let grid: String = contents
+            .chars()
+            .filter(|c| c.is_ascii_digit() || *c == '.')
+            .collect();
+        Ok(Board::try_from(grid.as_str())?)
+    }
+
+    pub fn from_api_string(value: &str) -> Result<Board, SudokuError> {
+        let grid = value.trim().trim_matches('"').trim().replace('.', "0");
+        Board::try_from(grid.as_str())
+    }
+
+    pub fn from_rows_str(rows: [&str; 9]) -> Result<Board, SudokuError> {
+        if let Some((row, line)) = rows
+            .iter()
+            .enumerate()
+            .find(|(_, line)| line.chars().count() != 9)
+        {
+            return Err(SudokuError::RowLength {
+                row,
+                length: line.chars().count(),
+            });
+        }
+        Board::try_from(rows.concat().as_str())
+    }
+
+    pub fn try_from_zero_based(value: &str, empty: char) -> Result<Board, SudokuError> {
+        let grid = value
+            .chars()
+            .map(|c| match c {
+                c if c == empty => Ok('0'),
+                '0'..='8' => Ok((c as u8 + 1) as char),
+                c => Err(SudokuError::InvalidCharacter(c)),
+            })
+            .collect::<Result<String, SudokuError>>()?;
+        Board::try_from(grid.as_str())
+    }
+
+    pub fn try_from_order(value: &str, order: Order) -> Result<Board, SudokuError> {
+        let board = Board::try_from(value)?;
+        match order {
+            Order::RowMajor => Ok(board),
+            Order::ColumnMajor => Ok(board.transpose()),
+        }
+    }
+
+    pub fn try_from_trimmed(value: &str) -> Result<Board, SudokuError> {
+        let grid: String = value.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+        Board::try_from(grid.as_str())
+    }
+
+    /// Retries from an empty board when random placements run into a dead end,
+    /// giving up after a fixed number of tries.
+    pub fn random_valid(filled: usize, rng: &mut impl Rng) -> Result<Board, SudokuError> {
+        if filled <= 81 {
+            for _ in 0..RANDOM_FILL_ATTEMPTS {
+                if let Some(board) = Board::try_random_fill(filled, rng) {
+                    return Ok(board);
+                }
+            }
+        }
+        Err(SudokuError::Unfillable(filled))
+    }
+
+    fn try_random_fill(filled: usize, rng: &mut impl Rng) -> Option<Board> {
+        let mut board = Board::new();
+        let mut positions: Vec<usize> = (0..81).collect();
+        positions.shuffle(rng);
+
+        let mut placed = 0;
+        for index in positions {
+            if placed == filled {
+                break;
+            }
+
+            let coordinates = (
+                Row::try_from(index / 9).unwrap(),
+                Column::try_from(index % 9).unwrap(),
+            );
+            let candidates: Vec<u8> = board.candidate_set(coordinates).iter().collect();
+            if let Some(&value) = candidates.choose(rng) {
+                board.set_cell(coordinates, value).unwrap();
+                placed += 1;
+            }
+        }
+
+        (placed == filled).then_some(board)
+    }
+
+    pub fn to_line_string(&self) -> String {
+        self.iter()
+            .map(|cell| match cell {
+                Cell::Empty => '0',
+                Cell::Value(num) => (b'0' + num) as char,
+            })
+            .collect()
+    }
+
+    pub fn to_bytes(&self) -> [u8; 41] {
+        let mut bytes = [0; 41];
+        self.iter().enumerate().for_each(|(i, cell)| {
+            let nibble = match cell {
+                Cell::Empty => 0,
+                Cell::Value(num) => num,
+            };
+            bytes[i / 2] |= if i % 2 == 0 { nibble << 4 } else { nibble };
+        });
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8; 41]) -> Result<Board, SudokuError> {
+        let mut cells = [[Cell::Empty; 9]; 9];
+        for (i, cell) in cells.iter_mut().flatten().enumerate() {
+            let byte = bytes[i / 2];
+            let nibble = if i % 2 == 0 { byte >> 4 } else { byte & 0x0f };
+            *cell = Cell::try_from(nibble)?;
+        }
+        Ok(Board::from_cells(cells))
+    }
+
+    pub fn to_option_grid(&self) -> [[Option<u8>; 9]; 9] {
+        self.0.map(|row| {
+            row.map(|cell| match cell {
+                Cell::Empty => None,
+                Cell::Value(val) => Some(val),
+            })
+        })
+    }
+
+    pub fn from_option_grid(grid: &[[Option<u8>; 9]; 9]) -> Result<Board, SudokuError> {
+        let mut board = [[Cell::Empty; 9]; 9];
+        for (cells, values) in board.iter_mut().zip(grid) {
+            for (cell, value) in cells.iter_mut().zip(values) {
+                *cell = match value {
+                    Some(0) => return Err(SudokuError::Value(0)),
+                    Some(value) => Cell::try_from(*value)?,
+                    None => Cell::Empty,
+                };
+            }
+        }
+        Ok(Board::from_cells(board))
+    }
+
+    /// Encodes clues as digits and each run of empty cells as a letter giving
+    /// its length (`a` = 1 up to `z` = 26), so `3_12__` becomes `3a12b`.
+    pub fn to_rle(&self) -> String {
+        let mut output = String::new();
+        let mut run = 0;
+        for cell in self.iter() {
+            match cell {
+                Cell::Empty => {
+                    run += 1;
+                    if run == 26 {
+                        output.push('z');
+                        run = 0;
+                    }
+                }
+                Cell::Value(val) => {
+                    if run > 0 {
+                        output.push((b'a' + run - 1) as char);
+                        run = 0;
+                    }
+                    output.push((b'0' + val) as char);
+                }
+            }
+        }
+        if run > 0 {
+            output.push((b'a' + run - 1) as char);
+        }
+        output
+    }
+
+    pub fn from_rle(value: &str) -> Result<Board, SudokuError> {
+        let mut grid = String::new();
+        for c in value.chars() {
+            match c {
+                '1'..='9' => grid.push(c),
+                'a'..='z' => (b'a'..=c as u8).for_each(|_| grid.push('0')),
+                c => return Err(SudokuError::InvalidCharacter(c)),
+            }
+        }
+        Board::try_from(grid.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Cell> + '_ {
+        self.0.iter().flatten().copied()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (Row, Column, Cell)> + '_ {
+        self.iter().enumerate().map(|(index, cell)| {
+            (
+                Row::try_from(index / 9).unwrap(),
+                Column::try_from(index % 9).unwrap(),
+                cell,
+            )
+        })
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = ((Row, Column), Cell)> + '_ {
+        self.entries()
+            .map(|(row, column, cell)| ((row, column), cell))
+    }
+
+    pub fn empty_cells(&self) -> impl Iterator<Item = (Row, Column)> + '_ {
+        self.cells()
+            .filter(|&(_, cell)| cell == Cell::Empty)
+            .map(|(coordinates, _)| coordinates)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Cell> {
+        self.1.stale = true;
+        self.0.iter_mut().flatten()
+    }
+
+    pub fn clue_symmetry(&self) -> Symmetry {
+        let is_clue = |row: usize, column: usize| self.0[row][column] != Cell::Empty;
+        let mirrored_by = |f: fn(usize, usize) -> (usize, usize)| {
+            (0..81).all(|index| {
+                let (row, column) = (index / 9, index % 9);
+                let (mirror_row, mirror_column) = f(row, column);
+                is_clue(row, column) == is_clue(mirror_row, mirror_column)
+            })
+        };
+
+        if mirrored_by(|row, column| (8 - row, 8 - column)) {
+            Symmetry::Rotational180
+        } else if mirrored_by(|row, column| (8 - row, column)) {
+            Symmetry::Horizontal
+        } else if mirrored_by(|row, column| (row, 8 - column)) {
+            Symmetry::Vertical
+        } else if mirrored_by(|row, column| (column, row)) {
+            Symmetry::Diagonal
+        } else {
+            Symmetry::None
+        }
+    }
+
+    pub fn format(&self, opts: &DisplayOptions) -> String {
+        let mut output = String::new();
+        for (i, row) in self.0.iter().enumerate() {
+            if opts.row_separator && (i == 3 || i == 6) {
+                if opts.box_separators {
+                    output.push_str("---+---+---\n");
+                } else {
+                    output.push_str("---------\n");
+                }
+            }
+            for (j, cell) in row.iter().enumerate() {
+                if opts.box_separators && (j == 3 || j == 6) {
+                    output.push('|');
+                }
+                match cell {
+                    Cell::Empty => output.push(opts.empty_char),
+                    Cell::Value(val) => output.push_str(&val.to_string()),
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    pub fn to_pretty_string(&self) -> String {
+        let mut output = String::new();
+        for (i, row) in self.0.iter().enumerate() {
+            if i % 3 == 0 {
+                output.push_str("+---+---+---+\n");
+            }
+            for (j, cell) in row.iter().enumerate() {
+                if j % 3 == 0 {
+                    output.push('|');
+                }
+                match cell {
+                    Cell::Empty => output.push('.'),
+                    Cell::Value(val) => output.push_str(&val.to_string()),
+                }
+            }
+            output.push_str("|\n");
+        }
+        output.push_str("+---+---+---+\n");
+        output
+    }
+
+    pub fn display_with_markers(&self) -> String {
+        let mut output = String::new();
+        for (row, column, cell) in self.entries() {
+            match cell {
+                Cell::Empty => output.push('_'),
+                Cell::Value(val) => {
+                    output.push_str(&val.to_string());
+                    if self.forbidden((row, column)).contains(val) {
+                        output.push('*');
+                    }
+                }
+            }
+            if column == Column::Nine {
+                output.push('\n');
+            }
+        }
+        output
+    }
+
+    pub fn display_labeled(&self) -> String {
+        let mut output = String::from(" ");
+        (1..=9).for_each(|column| output.push_str(&format!(" {}", column)));
+        output.push('\n');
+
+        for (index, row) in self.0.iter().enumerate() {
+            output.push_str(&format!("{:?}", Row::try_from(index).unwrap()));
+            for cell in row {
+                match cell {
+                    Cell::Empty => output.push_str(" _"),
+                    Cell::Value(val) => output.push_str(&format!(" {}", val)),
+                }
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+
+    pub fn mask_clues(&self, keep: &[[bool; 9]; 9]) -> Board {
+        Board::from_cells(array::from_fn(|row| {
+            array::from_fn(|column| {
+                if keep[row][column] {
+                    self.0[row][column]
+                } else {
+                    Cell::Empty
+                }
+            })
+        }))
+    }
+
+    pub fn changed_mask(&self, previous: &Board) -> [[bool; 9]; 9] {
+        array::from_fn(|row| {
+            array::from_fn(|column| self.0[row][column] != previous.0[row][column])
+        })
+    }
+
+    pub fn to_sdk(&self) -> String {
+        self.format(&DisplayOptions {
+            empty_char: '.',
+            ..DisplayOptions::default()
+        })
+    }
+
+    pub fn from_sdk(value: &str) -> Result<Board, SudokuError> {
+        let grid: String = value
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .collect::<String>()
+            .replace('.', "0");
+        Board::try_from_trimmed(&grid)
+    }
+
+    pub fn to_html_with_givens(&self, givens: &[[bool; 9]; 9]) -> String {
+        let mut output = String::from("<table class=\"sudoku\">\n");
+        for (cells, given_row) in self.0.iter().zip(givens) {
+            output.push_str("<tr>");
+            for (cell, &given) in cells.iter().zip(given_row) {
+                let class = if given { "given" } else { "entered" };
+                match cell {
+                    Cell::Empty => output.push_str(&format!("<td class=\"{}\"></td>", class)),
+                    Cell::Value(val) => {
+                        output.push_str(&format!("<td class=\"{}\">{}</td>", class, val))
+                    }
+                }
+            }
+            output.push_str("</tr>\n");
+        }
+        output.push_str("</table>\n");
+        output
+    }
+
+    pub fn with_highlight(&self, coordinate: (Row, Column)) -> String {
+        let (row, column) = (usize::from(coordinate.0), usize::from(coordinate.1));
+        let mut output = String::new();
+        for (i, cells) in self.0.iter().enumerate() {
+            for (j, cell) in cells.iter().enumerate() {
+                let symbol = match cell {
+                    Cell::Empty => '_',
+                    Cell::Value(val) => char::from(b'0' + val),
+                };
+                if (i, j) == (row, column) {
+                    output.push_str(&format!("[{}]", symbol));
+                } else {
+                    output.push_str(&format!(" {} ", symbol));
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    pub fn swap_digits(&mut self, a: u8, b: u8) -> Result<(), SudokuError> {
+        if let Some(&digit) = [a, b].iter().find(|digit| !(1..=9).contains(*digit)) {
+            return Err(SudokuError::Value(digit));
+        }
+
+        self.iter_mut().for_each(|cell| match *cell {
+            Cell::Value(val) if val == a => *cell = Cell::Value(b),
+            Cell::Value(val) if val == b => *cell = Cell::Value(a),
+            _ => {}
+        });
+        self.refresh_masks();
+        Ok(())
+    }
+
+    pub fn transpose(&self) -> Board {
+        Self::from_cells(array::from_fn(|row| {
+            array::from_fn(|column| self.0[column][row])
+        }))
+    }
+
+    pub fn scramble(&self, rng: &mut impl Rng) -> Board {
+        let board = if rng.gen() {
+            self.transpose()
+        } else {
+            self.clone()
+        };
+        let rows = random_line_permutation(rng);
+        let columns = random_line_permutation(rng);
+        let mut digits: Vec<u8> = (1..=9).collect();
+        digits.shuffle(rng);
+
+        Self::from_cells(array::from_fn(|row| {
+            array::from_fn(|column| match board.0[rows[row]][columns[column]] {
+                Cell::Empty => Cell::Empty,
+                Cell::Value(val) => Cell::Value(digits[val as usize - 1]),
+            })
+        }))
+    }
+
+    pub fn is_equivalent(&self, other: &Board) -> bool {
+        self.is_related_to(other, true)
+    }
+
+    pub fn is_geometrically_equivalent(&self, other: &Board) -> bool {
+        self.is_related_to(other, false)
+    }
+
+    fn is_related_to(&self, other: &Board, relabel: bool) -> bool {
+        if self.iter().filter(|&cell| cell != Cell::Empty).count()
+            != other.iter().filter(|&cell| cell != Cell::Empty).count()
+        {
+            return false;
+        }
+
+        let lines = line_permutations();
+        [self.clone(), self.transpose()].iter().any(|board| {
+            lines.iter().any(|rows| {
+                lines
+                    .iter()
+                    .any(|columns| board.matches_permuted(other, rows, columns, relabel))
+            })
+        })
+    }
+
+    fn matches_permuted(
+        &self,
+        other: &Board,
+        rows: &[usize; 9],
+        columns: &[usize; 9],
+        relabel: bool,
+    ) -> bool {
+        let mut mapping = [0; 10];
+        let mut inverse = [0; 10];
+        (0..81).all(|index| {
+            let (row, column) = (index / 9, index % 9);
+            match (self.0[rows[row]][columns[column]], other.0[row][column]) {
+                (Cell::Empty, Cell::Empty) => true,
+                (Cell::Value(a), Cell::Value(b)) if !relabel => a == b,
+                (Cell::Value(a), Cell::Value(b)) => {
+                    if mapping[a as usize] == 0 && inverse[b as usize] == 0 {
+                        mapping[a as usize] = b;
+                        inverse[b as usize] = a;
+                    }
+                    mapping[a as usize] == b
+                }
+                _ => false,
+            }
+        })
+    }
+
+    pub fn get_units_of(&self, coordinate: (Row, Column)) -> [[Cell; 9]; 3] {
+        [
+            self.get_row(coordinate.0),
+            self.get_column(coordinate.1),
+            self.get_square_of(coordinate),
+        ]
+    }
+}
+
+pub fn parse_pair(value: &str) -> Result<(Board, Board), SudokuError> {
+    let parts: Vec<&str> = value
+        .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect();
+    let [puzzle, solution] = parts[..] else {
+        return Err(SudokuError::PairFormat(parts.len()));
+    };
+
+    let puzzle = Board::from_api_string(puzzle)?;
+    let solution = Board::from_api_string(solution)?;
+    if !solution.is_valid_solution_of(&puzzle) {
+        return Err(SudokuError::SolutionMismatch);
+    }
+    Ok((puzzle, solution))
+}
+
+pub fn index_of(coordinate: (Row, Column)) -> usize {
+    usize::from(coordinate.0) * 9 + usize::from(coordinate.1)
+}
+
+pub fn coordinate_of(index: usize) -> Result<(Row, Column), SudokuError> {
+    if index >= 81 {
+        return Err(SudokuError::Index(index));
+    }
+    Ok((Row::try_from(index / 9)?, Column::try_from(index % 9)?))
+}
+
+fn parse_coordinates(token: &str) -> Option<(Row, Column)> {
+    let mut chars = token.chars();
+    let row = match chars.next()? {
+        c @ 'A'..='I' => Row::try_from((c as u8 - b'A') as usize).ok()?,
+        _ => return None,
+    };
+    let column = match chars.next()? {
+        c @ '1'..='9' => Column::try_from((c as u8 - b'1') as usize).ok()?,
+        _ => return None,
+    };
+    chars.next().is_none().then_some((row, column))
+}
+
+fn knight_moves(row: usize, column: usize) -> impl Iterator<Item = (usize, usize)> {
+    const OFFSETS: [(isize, isize); 8] = [
+        (-2, -1),
+        (-2, 1),
+        (-1, -2),
+        (-1, 2),
+        (1, -2),
+        (1, 2),
+        (2, -1),
+        (2, 1),
+    ];
+
+    OFFSETS.into_iter().filter_map(move |(dr, dc)| {
+        let row = row.checked_add_signed(dr).filter(|&row| row < 9)?;
+        let column = column.checked_add_signed(dc).filter(|&column| column < 9)?;
+        Some((row, column))
+    })
+}
+
+const RANDOM_FILL_ATTEMPTS: usize = 100;
+
+const ALL_DIGITS: u16 = 0b11_1111_1110;
+
+fn digit_bit(cell: Cell) -> u16 {
+    match cell {
+        Cell::Value(num) if (1..=9).contains(&num) => 1 << num,
+        _ => 0,
+    }
+}
+
+fn used_mask(cells: &[Cell]) -> u16 {
+    cells.iter().fold(0, |mask, &cell| mask | digit_bit(cell))
+}
+
+fn square_index(row: usize, column: usize) -> usize {
+    row / 3 * 3 + column / 3
+}
+
+fn peers_of(coordinates: (Row, Column)) -> Vec<(Row, Column)> {
+    let square = square_index(usize::from(coordinates.0), usize::from(coordinates.1));
+    let mut peers: Vec<(Row, Column)> = [
+        Unit::Row(coordinates.0),
+        Unit::Column(coordinates.1),
+        Unit::Square(square),
+    ]
+    .into_iter()
+    .flat_map(Board::unit_coordinates)
+    .filter(|&peer| peer != coordinates)
+    .collect();
+    peers.sort_by_key(|&peer| index_of(peer));
+    peers.dedup();
+    peers
+}
+
+fn digit_mask(cells: &[Cell]) -> Option<u16> {
+    cells.iter().try_fold(0, |mask, &cell| {
+        let bit = digit_bit(cell);
+        (mask & bit == 0).then_some(mask | bit)
+    })
+}
+
+fn is_completed(cells: [Cell; 9]) -> bool {
+    digit_mask(&cells) == Some(ALL_DIGITS)
+}
+
+fn fill_unit(
+    cells: &mut [Cell; 9],
+    empties: &[(usize, HashSet<u8>)],
+    completions: &mut Vec<[Cell; 9]>,
+) {
+    let Some(((index, candidates), rest)) = empties.split_first() else {
+        completions.push(*cells);
+        return;
+    };
+
+    for value in 1..=9 {
+        if candidates.contains(&value) && !cells.contains(&Cell::Value(value)) {
+            cells[*index] = Cell::Value(value);
+            fill_unit(cells, rest, completions);
+            cells[*index] = Cell::Empty;
+        }
+    }
+}
+
+fn no_duplicates(cells: [Cell; 9]) -> bool {
+    digit_mask(&cells).is_some()
+}
+
+fn line_permutations() -> Vec<[usize; 9]> {
+    const PERMUTATIONS: [[usize; 3]; 6] = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+
+    let mut lines = Vec::with_capacity(6 * 6 * 6 * 6);
+    for bands in PERMUTATIONS {
+        for first in PERMUTATIONS {
+            for second in PERMUTATIONS {
+                for third in PERMUTATIONS {
+                    let inner = [first, second, third];
+                    lines.push(array::from_fn(|i| bands[i / 3] * 3 + inner[i / 3][i % 3]));
+                }
+            }
+        }
+    }
+    lines
+}
+
+fn parse_cell(c: char) -> Result<Cell, SudokuError> {
+    match c {
+        '.' => Ok(Cell::Empty),
+        c => {
+            let digit = c.to_digit(10).ok_or(SudokuError::InvalidCharacter(c))?;
+            Cell::try_from(digit as u8)
+        }
+    }
+}
+
+fn random_line_permutation(rng: &mut impl Rng) -> [usize; 9] {
+    let mut bands = [0, 1, 2];
+    bands.shuffle(rng);
+    let mut inner = [[0, 1, 2]; 3];
+    inner.iter_mut().for_each(|lines| lines.shuffle(rng));
+    array::from_fn(|i| bands[i / 3] * 3 + inner[i / 3][i % 3])
+}
+
+impl PartialEq for Board {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Board {}
+
+impl std::hash::Hash for Board {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl std::fmt::Debug for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Board").field(&self.0).finish()
+    }
+}
+
+impl CandidateSet {
+    pub fn all() -> Self {
+        Self(ALL_DIGITS)
+    }
+
+    pub fn contains(&self, digit: u8) -> bool {
+        self.0 & digit_bit(Cell::Value(digit)) != 0
+    }
+
+    pub fn insert(&mut self, digit: u8) {
+        self.0 |= digit_bit(Cell::Value(digit));
+    }
+
+    pub fn remove(&mut self, digit: u8) {
+        self.0 &= !digit_bit(Cell::Value(digit));
+    }
+
+    pub fn toggle(&mut self, digit: u8) {
+        self.0 ^= digit_bit(Cell::Value(digit));
+    }
+
+    pub fn retain(&mut self, mut keep: impl FnMut(u8) -> bool) {
+        *self = self.iter().filter(|&digit| keep(digit)).collect();
+    }
+
+    pub fn intersection(&self, other: CandidateSet) -> CandidateSet {
+        Self(self.0 & other.0)
+    }
+
+    pub fn union(&self, other: CandidateSet) -> CandidateSet {
+        Self(self.0 | other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u8> {
+        let bits = self.0;
+        (1..=9).filter(move |digit| bits & 1 << digit != 0)
+    }
+}
+
+impl FromIterator<u8> for CandidateSet {
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        let mut set = Self::default();
+        iter.into_iter().for_each(|digit| set.insert(digit));
+        set
+    }
+}
+
+impl CandidateGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, coordinates: (Row, Column)) -> CandidateSet {
+        self.0[usize::from(coordinates.0)][usize::from(coordinates.1)]
+    }
+
+    pub fn toggle(&mut self, coordinates: (Row, Column), digit: u8) -> Result<(), SudokuError> {
+        if !(1..=9).contains(&digit) {
+            return Err(SudokuError::Value(digit));
+        }
+        self.0[usize::from(coordinates.0)][usize::from(coordinates.1)].toggle(digit);
+        Ok(())
+    }
+
+    pub fn candidate_positions(&self, unit: Unit, digit: u8) -> Vec<(Row, Column)> {
+        Board::unit_coordinates(unit)
+            .into_iter()
+            .filter(|&coordinates| self.get(coordinates).contains(digit))
+            .collect()
+    }
+
+    pub fn shared(&self, a: (Row, Column), b: (Row, Column)) -> CandidateSet {
+        self.get(a).intersection(self.get(b))
+    }
+
+    pub fn remove(&mut self, coordinates: (Row, Column), digit: u8) -> bool {
+        let candidates = &mut self.0[usize::from(coordinates.0)][usize::from(coordinates.1)];
+        let removed = candidates.contains(digit);
+        candidates.remove(digit);
+        removed
+    }
+
+    /// Clears the placed cell and removes `digit` from its peers, returning
+    /// the peers that lost it.
+    pub fn place(
+        &mut self,
+        coordinates: (Row, Column),
+        digit: u8,
+    ) -> Result<Vec<(Row, Column)>, SudokuError> {
+        if !(1..=9).contains(&digit) {
+            return Err(SudokuError::Value(digit));
+        }
+
+        self.0[usize::from(coordinates.0)][usize::from(coordinates.1)] = CandidateSet::default();
+        let eliminated: Vec<(Row, Column)> = peers_of(coordinates)
+            .into_iter()
+            .filter(|&peer| self.get(peer).contains(digit))
+            .collect();
+        for &(row, column) in &eliminated {
+            self.0[usize::from(row)][usize::from(column)].remove(digit);
+        }
+        Ok(eliminated)
+    }
+}
+
+impl FromIterator<((Row, Column), u8)> for Board {
+    fn from_iter<T: IntoIterator<Item = ((Row, Column), u8)>>(iter: T) -> Self {
+        let mut board = Board::new();
+        iter.into_iter().for_each(|(coordinates, number)| {
+            if let Ok(cell) = Cell::try_from(number) {
+                board.write(coordinates, cell);
+            }
+        });
+        board
+    }
+}
+
+impl TryFrom<&str> for Board {
+    type Error = SudokuError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if let Some(c) = value.chars().find(|c| !c.is_ascii()) {
+            return Err(SudokuError::InvalidCharacter(c));
+        }
+
+        if value.len() != 81 {
+            return Err(SudokuError::InputLength(value.len()));
+        }
+
+        let mut board = [[Cell::default(); 9]; 9];
+        for (i, c) in value.chars().enumerate() {
+            board[i / 9][i % 9] = parse_cell(c)?;
+        }
+
+        Ok(Self::from_cells(board))
+    }
+}
+
+impl FrozenBoard {
+    pub fn thaw(self) -> Board {
+        self.0
+    }
+}
+
+impl Deref for FrozenBoard {
+    type Target = Board;
+
+    fn deref(&self) -> &Board {
+        &self.0
+    }
+}
+
+impl Display for FrozenBoard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Cell {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(match self {
+            Cell::Empty => 0,
+            Cell::Value(num) => *num,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Cell {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u8::deserialize(deserializer)?;
+        Cell::try_from(value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Board {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_line_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Board {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let line = String::deserialize(deserializer)?;
+        Board::try_from(line.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.format(&DisplayOptions::default()))
+    }
+}
+
+impl Display for CandidateGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in &self.0 {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|candidates| {
+                    (1..=9)
+                        .map(|digit| {
+                            if candidates.contains(digit) {
+                                char::from(b'0' + digit)
+                            } else {
+                                '.'
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
+            writeln!(f, "{}", cells.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for CandidateGrid {
+    type Err = SudokuError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let cells: Vec<&str> = value.split_whitespace().collect();
+        if cells.len() != 81 {
+            return Err(SudokuError::InputLength(cells.len()));
+        }
+
+        let mut grid = CandidateGrid::new();
+        for (i, cell) in cells.into_iter().enumerate() {
+            if cell.len() != 9 {
+                return Err(SudokuError::CandidateLength {
+                    cell: i,
+                    length: cell.len(),
+                });
+            }
+            for (digit, c) in (1..=9).zip(cell.chars()) {
+                match c {
+                    '.' => {}
+                    c if c == char::from(b'0' + digit) => grid.0[i / 9][i % 9].insert(digit),
+                    c => return Err(SudokuError::InvalidCharacter(c)),
+                }
+            }
+        }
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn row_completed() {
+        let mut sudoku = Board::new();
+        sudoku.set_cell((Row::A, Column::One), 1).unwrap();
+        sudoku.set_cell((Row::A, Column::Two), 2).unwrap();
+        sudoku.set_cell((Row::A, Column::Three), 3).unwrap();
+        sudoku.set_cell((Row::A, Column::Four), 4).unwrap();
+        sudoku.set_cell((Row::A, Column::Five), 5).unwrap();
+        sudoku.set_cell((Row::A, Column::Six), 6).unwrap();
+        sudoku.set_cell((Row::A, Column::Seven), 7).unwrap();
+        sudoku.set_cell((Row::A, Column::Eight), 8).unwrap();
+        sudoku.set_cell((Row::A, Column::Nine), 9).unwrap();
+        assert!(sudoku.is_row_completed(Row::A))
+    }
+
+    #[test]
+    fn row_contains_empty_cell() {
+        let mut sudoku = Board::new();
+        sudoku.set_cell((Row::A, Column::One), 1).unwrap();
+
+        assert!(!sudoku.is_row_completed(Row::A))
+    }
+
+    #[test]
+    fn row_contains_duplicates() {
+        let mut sudoku = Board::new();
+        sudoku.set_cell((Row::A, Column::One), 1).unwrap();
+        sudoku.set_cell((Row::A, Column::Two), 1).unwrap();
+
+        assert!(!sudoku.is_row_completed(Row::A))
+    }
+
+    #[test]
+    fn column_completed() {
+        let mut sudoku = Board::new();
+        sudoku.set_cell((Row::A, Column::One), 1).unwrap();
+        sudoku.set_cell((Row::B, Column::One), 2).unwrap();
+        sudoku.set_cell((Row::C, Column::One), 3).unwrap();
+        sudoku.set_cell((Row::D, Column::One), 4).unwrap();
+        sudoku.set_cell((Row::E, Column::One), 5).unwrap();
+        sudoku.set_cell((Row::F, Column::One), 6).unwrap();
+        sudoku.set_cell((Row::G, Column::One), 7).unwrap();
+        sudoku.set_cell((Row::H, Column::One), 8).unwrap();
+        sudoku.set_cell((Row::I, Column::One), 9).unwrap();
+        assert!(sudoku.is_column_completed(Column::One))
+    }
+
+    #[test]
+    fn column_contains_empty_cell() {
+        let mut sudoku = Board::new();
+        sudoku.set_cell((Row::B, Column::Two), 1).unwrap();
+
+        assert!(!sudoku.is_column_completed(Column::Two))
+    }
+
+    #[test]
+    fn column_contains_duplicates() {
+        let mut sudoku = Board::new();
+        sudoku.set_cell((Row::A, Column::Two), 1).unwrap();
+        sudoku.set_cell((Row::B, Column::Two), 1).unwrap();
+
+        assert!(!sudoku.is_column_completed(Column::Two))
+    }
+
+    #[test]
+    fn square_completed() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        let board = Board::try_from(input).unwrap();
+
+        assert!(board.is_square_completed(4));
+        assert!(board.all_squares_completed());
+        assert!(!board.is_square_completed(9));
+    }
+
+    #[test]
+    fn square_contains_empty_cell() {
+        let mut sudoku = Board::new();
+        sudoku.set_cell((Row::A, Column::One), 1).unwrap();
+
+        assert!(!sudoku.is_square_completed(0));
+        assert!(!sudoku.all_squares_completed());
+    }
+
+    #[test]
+    fn square_contains_duplicates() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        let mut board = Board::try_from(input).unwrap();
+        board.set_cell((Row::B, Column::Two), 2).unwrap();
+
+        assert!(!board.is_square_completed(0));
+        assert!(board.is_square_completed(1));
+    }
+
+    #[test]
+    fn incomplete_board_is_valid() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+
+        assert!(board.is_valid());
+        assert!(!board.all_rows_completed());
+    }
+
+    #[test]
+    fn duplicate_in_box_is_invalid() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let mut board = Board::try_from(input).unwrap();
+        board.set_cell((Row::B, Column::Two), 3).unwrap();
+
+        assert!(!board.is_valid());
+    }
+
+    #[test]
+    fn all_rows_and_columns_completed() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        let board = Board::try_from(input).unwrap();
+
+        assert!(board.all_rows_completed());
+        assert!(board.all_columns_completed());
+    }
+
+    #[test]
+    fn row_not_completed() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        let mut board = Board::try_from(input).unwrap();
+
+        board.set_cell((Row::A, Column::One), 1).unwrap();
+        board.set_cell((Row::A, Column::Two), 1).unwrap();
+
+        assert!(!board.all_rows_completed());
+    }
+
+    #[test]
+    fn cloned_board_is_independent() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+        assert_eq!(board, Board::try_from(input).unwrap());
+
+        let mut copy = board.clone();
+        copy.set_cell((Row::A, Column::One), 4).unwrap();
+        assert_ne!(copy, board);
+        assert_eq!(board.get_cell((Row::A, Column::One)), Some(Cell::Empty));
+    }
+
+    #[test]
+    fn iterate_cells_with_coordinates() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+
+        assert_eq!(board.cells().count(), 81);
+        assert_eq!(
+            board.cells().nth(2),
+            Some(((Row::A, Column::Three), Cell::Value(3)))
+        );
+        assert_eq!(board.empty_cells().count(), input.matches('0').count());
+        assert_eq!(board.empty_cells().next(), Some((Row::A, Column::One)));
+    }
+
+    #[test]
+    fn dedup_boards_in_hash_set() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let mut boards = HashSet::new();
+        boards.insert(Board::try_from(input).unwrap());
+        boards.insert(Board::try_from(input).unwrap());
+        assert_eq!(boards.len(), 1);
+
+        boards.insert(Board::new());
+        assert_eq!(boards.len(), 2);
+    }
+
+    #[test]
+    fn set_and_clear_cells() {
+        let mut board = Board::new();
+        board.set((Row::C, Column::Four), Cell::Value(8)).unwrap();
+        assert_eq!(board.get_cell((Row::C, Column::Four)), Some(Cell::Value(8)));
+
+        board.clear_cell((Row::C, Column::Four));
+        assert_eq!(board.get_cell((Row::C, Column::Four)), Some(Cell::Empty));
+
+        assert!(matches!(
+            board.set_cell((Row::A, Column::One), 10),
+            Err(SudokuError::Value(10))
+        ));
+        assert!(matches!(
+            board.set((Row::A, Column::One), Cell::Value(0)),
+            Err(SudokuError::Value(0))
+        ));
+        assert_eq!(board, Board::new());
+    }
+
+    #[test]
+    fn get_cell_reads_set_cell() {
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::One), 1).unwrap();
+
+        assert_eq!(board.get_cell((Row::A, Column::One)), Some(Cell::Value(1)));
+        assert_eq!(board.get_cell((Row::I, Column::Nine)), Some(Cell::Empty));
+    }
+
+    #[test]
+    fn units_of_cell() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        let board = Board::try_from(input).unwrap();
+        let [row, column, square] = board.get_units_of((Row::E, Column::Five)).map(|unit| {
+            unit.map(|cell| match cell {
+                Cell::Empty => 0,
+                Cell::Value(num) => num,
+            })
+        });
+
+        assert_eq!(row, [6, 4, 1, 2, 9, 7, 8, 3, 5]);
+        assert_eq!(column, [5, 3, 7, 8, 9, 6, 2, 1, 4]);
+        assert_eq!(square, [3, 8, 1, 2, 9, 7, 5, 6, 4]);
+    }
+
+    #[test]
+    fn candidates_of_constrained_and_open_cells() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748906";
+        let board = Board::try_from(input).unwrap();
+
+        assert_eq!(
+            board.candidates((Row::I, Column::Eight)),
+            HashSet::from([1])
+        );
+        assert!(board.candidates((Row::A, Column::One)).is_empty());
+        assert_eq!(
+            Board::new().candidates((Row::E, Column::Five)),
+            (1..=9).collect()
+        );
+    }
+
+    #[test]
+    fn unit_masks_follow_every_write() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let mut board = Board::try_from(input).unwrap();
+        board.set_cell((Row::A, Column::One), 4).unwrap();
+        board.set_cell((Row::A, Column::One), 5).unwrap();
+        board.clear_cell((Row::A, Column::Three));
+        board.apply_moves_from_str("B2=7").unwrap();
+        board.row_mut(Row::E)[1] = Cell::Value(3);
+        board.set_cell((Row::I, Column::Nine), 8).unwrap();
+
+        let expected = |board: &Board, coordinates: (Row, Column)| {
+            let used: HashSet<Cell> = board
+                .get_units_of(coordinates)
+                .into_iter()
+                .flatten()
+                .collect();
+            (1..=9)
+                .filter(|&num| !used.contains(&Cell::Value(num)))
+                .collect::<HashSet<u8>>()
+        };
+        for coordinates in board.empty_cells() {
+            assert_eq!(board.candidates(coordinates), expected(&board, coordinates));
+        }
+        let rebuilt = Board::try_from(board.to_line_string().as_str()).unwrap();
+        assert_eq!(rebuilt.1.rows, board.1.rows);
+        assert_eq!(rebuilt.1.columns, board.1.columns);
+        assert_eq!(rebuilt.1.squares, board.1.squares);
+    }
+
+    #[test]
+    fn candidate_set_tracks_digits() {
+        let mut set = CandidateSet::from_iter([1, 5, 9]);
+        set.insert(3);
+        set.remove(5);
+
+        assert_eq!(set.iter().collect::<Vec<u8>>(), vec![1, 3, 9]);
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(9) && !set.contains(5) && !set.contains(0));
+        assert_eq!(CandidateSet::all().len(), 9);
+        assert!(CandidateSet::default().is_empty());
+    }
+
+    #[test]
+    fn toggled_pencil_mark_flips_membership() {
+        let mut grid = CandidateGrid::new();
+        grid.toggle((Row::C, Column::Four), 7).unwrap();
+        assert!(grid.get((Row::C, Column::Four)).contains(7));
+        assert!(grid.get((Row::C, Column::Five)).is_empty());
+
+        grid.toggle((Row::C, Column::Four), 7).unwrap();
+        assert!(!grid.get((Row::C, Column::Four)).contains(7));
+        assert!(matches!(
+            grid.toggle((Row::C, Column::Four), 0),
+            Err(SudokuError::Value(0))
+        ));
+        assert_eq!(
+            Board::new().candidate_grid().get((Row::E, Column::Five)),
+            CandidateSet::all()
+        );
+    }
+
+    #[test]
+    fn candidate_grid_display_packs_digits() {
+        let mut grid = CandidateGrid::new();
+        for digit in [1, 2, 4, 8] {
+            grid.toggle((Row::A, Column::One), digit).unwrap();
+        }
+        grid.toggle((Row::I, Column::Nine), 9).unwrap();
+
+        let output = grid.to_string();
+        let lines: Vec<&str> = output.lines().collect();
+        let empty = ["........."; 9].join(" ");
+        assert_eq!(lines.len(), 9);
+        assert!(lines[0].starts_with("12.4...8. ........."));
+        assert_eq!(lines[4], empty);
+        assert!(lines[8].ends_with("......... ........9"));
+    }
+
+    #[test]
+    fn placing_clears_digit_from_peers() {
+        let mut board = Board::new();
+        board.set_cell((Row::B, Column::Two), 5).unwrap();
+        let mut grid = board.candidate_grid();
+
+        let eliminated = grid.place((Row::E, Column::Five), 5).unwrap();
+        assert_eq!(eliminated.len(), 18);
+        assert!(!eliminated.contains(&(Row::B, Column::Five)));
+        assert!(!eliminated.contains(&(Row::E, Column::Two)));
+        assert!(eliminated.contains(&(Row::E, Column::One)));
+        assert!(eliminated.contains(&(Row::A, Column::Five)));
+        assert!(eliminated.contains(&(Row::F, Column::Six)));
+        assert!(grid.get((Row::E, Column::Five)).is_empty());
+        assert!(!grid.get((Row::I, Column::Five)).contains(5));
+        assert_eq!(grid.get((Row::A, Column::One)).len(), 8);
+        assert_eq!(grid.get((Row::I, Column::Nine)), CandidateSet::all());
+    }
+
+    #[test]
+    fn candidate_grid_round_trips_through_text() {
+        let input =
+            "..3020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let mut grid = Board::try_from(input).unwrap().candidate_grid();
+        grid.toggle((Row::A, Column::One), 9).unwrap();
+        grid.toggle((Row::I, Column::Nine), 4).unwrap();
+
+        let text = grid.to_string();
+        assert_eq!(text.parse::<CandidateGrid>().unwrap(), grid);
+        assert!(matches!(
+            text.replacen('.', "5", 1).parse::<CandidateGrid>(),
+            Err(SudokuError::InvalidCharacter('5'))
+        ));
+        assert!(matches!(
+            "12.4...8.".parse::<CandidateGrid>(),
+            Err(SudokuError::InputLength(1))
+        ));
+        assert!(matches!(
+            text.replacen(' ', ". ", 1).parse::<CandidateGrid>(),
+            Err(SudokuError::CandidateLength {
+                cell: 0,
+                length: 10
+            })
+        ));
+    }
+
+    #[test]
+    fn shared_candidates_of_two_cells() {
+        let mut grid = CandidateGrid::new();
+        for digit in [1, 4, 7] {
+            grid.toggle((Row::A, Column::One), digit).unwrap();
+        }
+        for digit in [4, 7, 9] {
+            grid.toggle((Row::F, Column::Six), digit).unwrap();
+        }
+
+        let shared = grid.shared((Row::A, Column::One), (Row::F, Column::Six));
+        assert_eq!(shared, CandidateSet::from_iter([4, 7]));
+        assert!(grid
+            .shared((Row::A, Column::One), (Row::B, Column::Two))
+            .is_empty());
+    }
+
+    #[test]
+    fn propagation_tightens_forced_cells() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+        let plain = board.candidate_grid();
+        let propagated = board.propagated_candidates();
+
+        let (forced, digit) = board
+            .hidden_singles()
+            .into_iter()
+            .find(|&(coordinates, _)| plain.get(coordinates).len() > 1)
+            .unwrap();
+        assert_eq!(propagated.get(forced), CandidateSet::from_iter([digit]));
+
+        for (coordinates, digit) in board.naked_singles() {
+            assert!(peers_of(coordinates)
+                .into_iter()
+                .all(|peer| !propagated.get(peer).contains(digit)));
+        }
+    }
+
+    #[test]
+    fn checked_placement_rejects_conflict() {
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::One), 5).unwrap();
+
+        assert!(matches!(
+            board.set_cell_checked((Row::A, Column::Nine), 5),
+            Err(SudokuError::Conflict {
+                row: Row::A,
+                column: Column::Nine,
+                value: 5
+            })
+        ));
+        assert_eq!(board.get_cell((Row::A, Column::Nine)), Some(Cell::Empty));
+
+        board.set_cell_checked((Row::A, Column::Nine), 6).unwrap();
+        assert_eq!(board.get_cell((Row::A, Column::Nine)), Some(Cell::Value(6)));
+    }
+
+    #[test]
+    fn square_of_corner_and_center_boxes() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        let board = Board::try_from(input).unwrap();
+        let square = |coordinate| {
+            board.get_square_of(coordinate).map(|cell| match cell {
+                Cell::Empty => 0,
+                Cell::Value(num) => num,
+            })
+        };
+
+        assert_eq!(square((Row::B, Column::Two)), [1, 2, 3, 5, 7, 8, 4, 9, 6]);
+        assert_eq!(square((Row::A, Column::Nine)), [7, 8, 9, 6, 2, 4, 1, 5, 3]);
+        assert_eq!(square((Row::E, Column::Five)), [3, 8, 1, 2, 9, 7, 5, 6, 4]);
+        assert_eq!(square((Row::I, Column::One)), [7, 1, 9, 8, 6, 4, 2, 3, 5]);
+        assert_eq!(square((Row::G, Column::Eight)), [5, 4, 8, 3, 7, 2, 9, 1, 6]);
+    }
+
+    #[test]
+    fn empty_cells_sorted_by_candidate_count() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+
+        let cells = board.empty_cells_by_difficulty();
+        let min = cells.iter().map(|&(_, count)| count).min().unwrap();
+
+        assert_eq!(cells.len(), input.chars().filter(|&c| c == '0').count());
+        assert_eq!(cells[0].1, min);
+        assert!(cells.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+
+    #[test]
+    fn assert_solution_reports_mismatches() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        let expected = Board::try_from(input).unwrap();
+        let mut board = expected.clone();
+
+        assert_eq!(board.assert_solution(&expected), Ok(()));
+
+        board.set_cell((Row::C, Column::Seven), 0).unwrap();
+        assert_eq!(
+            board.assert_solution(&expected),
+            Err(vec![(Row::C, Column::Seven)])
+        );
+    }
+
+    #[test]
+    fn grade_submitted_solutions() {
+        let puzzle = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let correct = Board::try_from(
+            "483921657967345821251876493548132976729564138136798245372689514814253769695417382",
+        )
+        .unwrap();
+        assert!(correct.is_valid_solution_of(&puzzle));
+
+        let mut broken = correct.clone();
+        broken.set_cell((Row::A, Column::One), 9).unwrap();
+        assert!(!broken.is_valid_solution_of(&puzzle));
+
+        let other = Board::try_from(
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916",
+        )
+        .unwrap();
+        assert!(other.validate_complete().is_ok());
+        assert!(!other.is_valid_solution_of(&puzzle));
+    }
+
+    #[test]
+    fn progress_of_unit() {
+        let mut board = Board::new();
+        (1..=4).for_each(|value| {
+            let column = Column::try_from(value as usize - 1).unwrap();
+            board.set_cell((Row::A, column), value).unwrap();
+        });
+        let full = Board::try_from(
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916",
+        )
+        .unwrap();
+
+        assert!((board.unit_progress(Unit::Row(Row::A)) - 4.0 / 9.0).abs() < f32::EPSILON);
+        assert_eq!(board.unit_progress(Unit::Row(Row::B)), 0.0);
+        assert_eq!(full.unit_progress(Unit::Square(4)), 1.0);
+    }
+
+    #[test]
+    fn completion_report_with_only_rows_complete() {
+        let board = Board::try_from("123456789".repeat(9).as_str()).unwrap();
+        let report = board.completion_report();
+
+        assert_eq!(report.len(), 27);
+        for (unit, completed) in report {
+            assert_eq!(completed, matches!(unit, Unit::Row(_)));
+        }
+    }
+
+    #[test]
+    fn report_duplicates_in_unit() {
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::Three), 4).unwrap();
+        board.set_cell((Row::F, Column::Three), 4).unwrap();
+        board.set_cell((Row::I, Column::Three), 7).unwrap();
+
+        assert_eq!(
+            board.duplicates_in_unit(Unit::Column(Column::Three)),
+            vec![4]
+        );
+        assert!(board.duplicates_in_unit(Unit::Row(Row::A)).is_empty());
+        assert!(board.duplicates_in_unit(Unit::Square(0)).is_empty());
+    }
+
+    #[test]
+    fn constrained_cells_score_higher() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+        let scores = board.constraint_scores();
+
+        assert_eq!(scores[0][2], 0);
+        assert!(scores[3][4] > scores[0][0]);
+        assert_eq!(Board::new().constraint_scores(), [[0; 9]; 9]);
+
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::Two), 1).unwrap();
+        board.set_cell((Row::B, Column::One), 2).unwrap();
+        board.set_cell((Row::I, Column::One), 3).unwrap();
+        board.set_cell((Row::E, Column::Five), 4).unwrap();
+        let scores = board.constraint_scores();
+
+        assert_eq!(scores[0][0], 3);
+        assert_eq!(scores[8][8], 1);
+    }
+
+    #[test]
+    fn forbidden_complements_candidates() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+        let coordinates = (Row::B, Column::Two);
+
+        let candidates = board.candidate_set(coordinates);
+        let forbidden = board.forbidden(coordinates);
+
+        assert!(candidates.intersection(forbidden).is_empty());
+        assert_eq!(candidates.union(forbidden), CandidateSet::all());
+        assert!(!board.forbidden((Row::A, Column::Three)).contains(3));
+        assert!(board.forbidden((Row::A, Column::Three)).contains(2));
+    }
+
+    #[test]
+    fn coordinates_of_units() {
+        assert_eq!(
+            Board::unit_coordinates(Unit::Square(0)),
+            [
+                (Row::A, Column::One),
+                (Row::A, Column::Two),
+                (Row::A, Column::Three),
+                (Row::B, Column::One),
+                (Row::B, Column::Two),
+                (Row::B, Column::Three),
+                (Row::C, Column::One),
+                (Row::C, Column::Two),
+                (Row::C, Column::Three),
+            ]
+        );
+
+        let row = Board::unit_coordinates(Unit::Row(Row::B));
+        assert!(row.iter().all(|&(r, _)| r == Row::B));
+        assert_eq!(row[0], (Row::B, Column::One));
+        assert_eq!(row[8], (Row::B, Column::Nine));
+        assert_eq!(
+            Board::unit_coordinates(Unit::Square(8))[4],
+            (Row::H, Column::Eight)
+        );
+    }
+
+    #[test]
+    fn unit_completions_with_two_empties() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719620048864910072235748916";
+        let board = Board::try_from(input).unwrap();
+
+        let row = board.get_row(Row::G);
+        let (mut first, mut second) = (row, row);
+        (first[5], first[6]) = (Cell::Value(3), Cell::Value(5));
+        (second[5], second[6]) = (Cell::Value(5), Cell::Value(3));
+        assert_eq!(
+            board.unit_completions(Unit::Row(Row::G)),
+            vec![first, second]
+        );
+
+        let mut forced = Board::try_from(
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916",
+        )
+        .unwrap();
+        forced.set_cell((Row::A, Column::One), 0).unwrap();
+        forced.set_cell((Row::A, Column::Two), 0).unwrap();
+        assert_eq!(
+            forced.unit_completions(Unit::Row(Row::A)),
+            vec![Board::try_from(input).unwrap().get_row(Row::A)]
+        );
+    }
+
+    #[test]
+    fn conflicts_grouped_by_unit() {
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::Three), 4).unwrap();
+        board.set_cell((Row::F, Column::Three), 4).unwrap();
+        board.set_cell((Row::E, Column::Seven), 2).unwrap();
+        board.set_cell((Row::E, Column::Nine), 2).unwrap();
+
+        assert_eq!(
+            board.conflicts_by_unit(),
+            vec![
+                (Unit::Row(Row::E), vec![2]),
+                (Unit::Column(Column::Three), vec![4]),
+                (Unit::Square(5), vec![2]),
+            ]
+        );
+        assert!(Board::new().conflicts_by_unit().is_empty());
+    }
+
+    #[test]
+    fn first_conflict_in_column() {
+        let mut board = Board::new();
+        board.set_cell((Row::B, Column::Four), 6).unwrap();
+        board.set_cell((Row::H, Column::Four), 6).unwrap();
+        board.set_cell((Row::E, Column::Seven), 2).unwrap();
+        board.set_cell((Row::F, Column::Eight), 2).unwrap();
+
+        assert_eq!(
+            board.first_conflict(),
+            Some((Unit::Column(Column::Four), 6))
+        );
+        assert_eq!(Board::new().first_conflict(), None);
+    }
+
+    #[test]
+    fn report_naked_singles_without_filling() {
+        let puzzle = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let solution = Board::try_from(
+            "483921657967345821251876493548132976729564138136798245372689514814253769695417382",
+        )
+        .unwrap();
+        let singles = puzzle.naked_singles();
+
+        assert!(!singles.is_empty());
+        assert!(singles.iter().all(
+            |&(coordinates, value)| solution.get_cell(coordinates) == Some(Cell::Value(value))
+        ));
+        assert_eq!(puzzle.clue_count(), 32);
+        assert!(solution.naked_singles().is_empty());
+    }
+
+    #[test]
+    fn report_hidden_single_once() {
+        let mut board = Board::new();
+        board.set_cell((Row::B, Column::Four), 1).unwrap();
+        board.set_cell((Row::C, Column::Seven), 1).unwrap();
+        board.set_cell((Row::D, Column::Two), 1).unwrap();
+        board.set_cell((Row::G, Column::Three), 1).unwrap();
+        let singles = board.hidden_singles();
+
+        assert!(board.candidates((Row::A, Column::One)).len() > 1);
+        assert_eq!(
+            singles
+                .iter()
+                .filter(|&&single| single == ((Row::A, Column::One), 1))
+                .count(),
+            1
+        );
+
+        let solved = Board::try_from(
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916",
+        )
+        .unwrap();
+        assert!(solved.hidden_singles().is_empty());
+    }
+
+    #[test]
+    fn find_bivalue_cells() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719620048864910072235748916";
+        let board = Board::try_from(input).unwrap();
+        let mut candidates = board.candidate_grid();
+
+        assert_eq!(
+            board.bivalue_cells(&candidates),
+            vec![
+                ((Row::G, Column::Six), (3, 5)),
+                ((Row::G, Column::Seven), (3, 5)),
+                ((Row::H, Column::Six), (3, 5)),
+                ((Row::H, Column::Seven), (3, 5)),
+            ]
+        );
+
+        candidates.toggle((Row::G, Column::Six), 3).unwrap();
+        candidates.toggle((Row::A, Column::One), 1).unwrap();
+        candidates.toggle((Row::A, Column::One), 2).unwrap();
+        assert_eq!(board.bivalue_cells(&candidates).len(), 3);
+        assert!(!board
+            .bivalue_cells(&candidates)
+            .contains(&((Row::G, Column::Six), (3, 5))));
+        assert!(Board::new()
+            .bivalue_cells(&Board::new().candidate_grid())
+            .is_empty());
+    }
+
+    #[test]
+    fn positions_of_digit_in_unit() {
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::One), 5).unwrap();
+        board.set_cell((Row::A, Column::Two), 3).unwrap();
+        board.set_cell((Row::D, Column::Three), 7).unwrap();
+        board.set_cell((Row::F, Column::Five), 7).unwrap();
+        let mut candidates = board.candidate_grid();
+
+        assert_eq!(
+            candidates.candidate_positions(Unit::Row(Row::A), 7),
+            vec![
+                (Row::A, Column::Four),
+                (Row::A, Column::Six),
+                (Row::A, Column::Seven),
+                (Row::A, Column::Eight),
+                (Row::A, Column::Nine),
+            ]
+        );
+        assert!(candidates
+            .candidate_positions(Unit::Square(3), 7)
+            .is_empty());
+
+        candidates.toggle((Row::A, Column::Six), 7).unwrap();
+        assert!(!candidates
+            .candidate_positions(Unit::Row(Row::A), 7)
+            .contains(&(Row::A, Column::Six)));
+    }
+
+    #[test]
+    fn digit_confined_to_one_row_of_box() {
+        let mut board = Board::new();
+        board.set_cell((Row::B, Column::Four), 1).unwrap();
+        board.set_cell((Row::C, Column::Seven), 1).unwrap();
+
+        let forced = board.box_forced_rows();
+
+        assert!(forced.contains(&(0, 1, Row::A)));
+        assert!(!forced
+            .iter()
+            .any(|&(square, digit, _)| square == 1 && digit == 1));
+        assert!(!forced
+            .iter()
+            .any(|&(square, digit, _)| square == 0 && digit == 2));
+        assert!(Board::new().box_forced_rows().is_empty());
+    }
+
+    #[test]
+    fn count_placed_digits() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+        let counts = board.digit_counts();
+
+        for digit in 1..=9u8 {
+            let expected = input
+                .chars()
+                .filter(|&c| c == (b'0' + digit) as char)
+                .count();
+            assert_eq!(counts[digit as usize - 1] as usize, expected);
+        }
+        assert_eq!(counts[0], 4);
+        assert_eq!(Board::new().digit_counts(), [0; 9]);
+    }
+
+    #[test]
+    fn status_line_for_each_state() {
+        let solved = Board::try_from(
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916",
+        )
+        .unwrap();
+        assert_eq!(solved.status_line(), "solved");
+
+        let mut partial = solved.clone();
+        (0..9).for_each(|column| {
+            let column = Column::try_from(column).unwrap();
+            partial.set_cell((Row::A, column), 0).unwrap();
+        });
+        partial.set_cell((Row::B, Column::One), 0).unwrap();
+        partial.set_cell((Row::B, Column::Two), 0).unwrap();
+        partial.set_cell((Row::B, Column::Three), 0).unwrap();
+        assert_eq!(partial.status_line(), "valid, 12 empty");
+
+        let mut broken = Board::new();
+        broken.set_cell((Row::A, Column::One), 4).unwrap();
+        broken.set_cell((Row::A, Column::Five), 4).unwrap();
+        assert_eq!(broken.status_line(), "invalid: 1 conflicts");
+        broken.set_cell((Row::C, Column::Two), 4).unwrap();
+        assert_eq!(broken.status_line(), "invalid: 2 conflicts");
+    }
+
+    #[test]
+    fn entropy_grows_with_open_cells() {
+        let mut near_solved = Board::try_from(
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916",
+        )
+        .unwrap();
+        near_solved.set_cell((Row::E, Column::Five), 0).unwrap();
+        let empty = Board::new().candidate_entropy();
+
+        assert_eq!(near_solved.candidate_entropy(), 0.0);
+        assert!((empty - 81.0 * 9f32.log2()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn dead_cells_have_no_candidates() {
+        let input =
+            "023456789000000000000000000100000000000000000000000000000000000000000000000000000";
+        let board = Board::try_from(input).unwrap();
+
+        assert_eq!(board.dead_cells(), vec![(Row::A, Column::One)]);
+        assert!(Board::new().dead_cells().is_empty());
+    }
+
+    #[test]
+    fn swap_digits_keeps_solution_valid() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        let mut board = Board::try_from(input).unwrap();
+        board.swap_digits(1, 2).unwrap();
+
+        let count = |board: &Board, digit| {
+            board
+                .iter()
+                .filter(|&cell| cell == Cell::Value(digit))
+                .count()
+        };
+        assert_eq!(board.get_cell((Row::A, Column::One)), Some(Cell::Value(2)));
+        assert_eq!(board.get_cell((Row::A, Column::Two)), Some(Cell::Value(1)));
+        assert_eq!((count(&board, 1), count(&board, 2)), (9, 9));
+        assert!(board.validate_complete().is_ok());
+        assert!(matches!(
+            board.swap_digits(0, 3),
+            Err(SudokuError::Value(0))
+        ));
+    }
+
+    #[test]
+    fn transpose_twice_is_identity() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+
+        assert_ne!(board.transpose(), board);
+        assert_eq!(board.transpose().transpose(), board);
+    }
+
+    #[test]
+    fn transpose_keeps_board_solved() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        let board = Board::try_from(input).unwrap().transpose();
+
+        assert!(board.all_rows_completed());
+        assert!(board.all_columns_completed());
+    }
+
+    #[test]
+    fn iter_counts_empty_cells() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+
+        assert_eq!(board.iter().count(), 81);
+        assert_eq!(
+            board.iter().filter(|&cell| cell == Cell::Empty).count(),
+            input.chars().filter(|&c| c == '0').count()
+        );
+    }
+
+    #[test]
+    fn iter_mut_modifies_cells() {
+        let mut board = Board::new();
+        board.iter_mut().for_each(|cell| *cell = Cell::Value(1));
+
+        assert!(board.iter().all(|cell| cell == Cell::Value(1)));
+    }
+
+    #[test]
+    fn parse_quoted_api_string() {
+        let input = "  \"..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..\"\n";
+        let expected =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::from_api_string(input).unwrap();
+
+        assert_eq!(
+            board.to_string(),
+            Board::try_from(expected).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn parse_from_row_strings() {
+        let board = Board::from_rows_str([
+            "003020600",
+            "900305001",
+            "001806400",
+            "008102900",
+            "700000008",
+            "006708200",
+            "002609500",
+            "800203009",
+            "005010300",
+        ])
+        .unwrap();
+        let expected = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+
+        assert_eq!(board, expected);
+
+        let mut rows = ["000000000"; 9];
+        rows[4] = "0000000000";
+        assert!(matches!(
+            Board::from_rows_str(rows),
+            Err(SudokuError::RowLength { row: 4, length: 10 })
+        ));
+    }
+
+    #[test]
+    fn parse_zero_based_digits() {
+        let one_based =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let zero_based: String = one_based
+            .chars()
+            .map(|c| match c {
+                '0' => '.',
+                c => (c as u8 - 1) as char,
+            })
+            .collect();
+
+        let board = Board::try_from_zero_based(&zero_based, '.').unwrap();
+
+        assert_eq!(
+            board.to_string(),
+            Board::try_from(one_based).unwrap().to_string()
+        );
+        assert!(matches!(
+            Board::try_from_zero_based(&zero_based.replace('.', "_"), '.'),
+            Err(SudokuError::InvalidCharacter('_'))
+        ));
+    }
+
+    #[test]
+    fn parse_column_major_order() {
+        let row_major =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let column_major: String = (0..81)
+            .map(|index| row_major.as_bytes()[(index % 9) * 9 + index / 9] as char)
+            .collect();
+
+        let board = Board::try_from_order(row_major, Order::RowMajor).unwrap();
+        let transposed = Board::try_from_order(&column_major, Order::ColumnMajor).unwrap();
+
+        assert_eq!(board, transposed);
+        assert_eq!(
+            Board::try_from(column_major.as_str())
+                .unwrap()
+                .transpose()
+                .to_string(),
+            board.to_string()
+        );
+    }
+
+    #[test]
+    fn parse_with_surrounding_and_embedded_whitespace() {
+        let input = "\n  003020600\n900305001\n001806400\n008102900\n700000008\n\
+                     006708200\n002609500\n800203009\n005010300\t\r\n";
+        let expected =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from_trimmed(input).unwrap();
+
+        assert_eq!(
+            board.to_string(),
+            Board::try_from(expected).unwrap().to_string()
+        );
+        assert!(matches!(
+            Board::try_from_trimmed(" 123\n456 "),
+            Err(SudokuError::InputLength(6))
+        ));
+    }
+
+    #[test]
+    fn rotationally_symmetric_clues() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+
+        assert_eq!(board.clue_symmetry(), Symmetry::Rotational180);
+    }
+
+    #[test]
+    fn mirrored_clues() {
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::One), 1).unwrap();
+        board.set_cell((Row::A, Column::Nine), 2).unwrap();
+        assert_eq!(board.clue_symmetry(), Symmetry::Vertical);
+
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::Two), 1).unwrap();
+        board.set_cell((Row::B, Column::One), 2).unwrap();
+        assert_eq!(board.clue_symmetry(), Symmetry::Diagonal);
+    }
+
+    #[test]
+    fn asymmetric_clues() {
+        let input =
+            "400000805030000000000700000020000060000080400000010000000603070500200000104000000";
+        let board = Board::try_from(input).unwrap();
+
+        assert_eq!(board.clue_symmetry(), Symmetry::None);
+    }
+
+    #[test]
+    fn random_valid_board() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for filled in [0, 17, 30, 50] {
+            let board = Board::random_valid(filled, &mut rng).unwrap();
+
+            assert!(board.is_valid());
+            assert_eq!(board.clue_count(), filled);
+        }
+        assert!(matches!(
+            Board::random_valid(82, &mut rng),
+            Err(SudokuError::Unfillable(82))
+        ));
+    }
+
+    #[test]
+    fn missing_digits_in_units() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        let board = Board::try_from(input).unwrap();
+        assert!(board.missing_in_row(Row::A).is_empty());
+        assert!(board.missing_in_column(Column::Five).is_empty());
+        assert!(board.missing_in_square((Row::E, Column::Five)).is_empty());
+
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::One), 5).unwrap();
+        assert_eq!(
+            board.missing_in_row(Row::A),
+            CandidateSet::from_iter([1, 2, 3, 4, 6, 7, 8, 9])
+        );
+        assert_eq!(
+            board.missing_in_column(Column::One),
+            CandidateSet::from_iter([1, 2, 3, 4, 6, 7, 8, 9])
+        );
+        assert_eq!(board.missing_in_square((Row::I, Column::Nine)).len(), 9);
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+
+        let bytes = board.to_bytes();
+        assert_eq!(bytes[0], 0x00);
+        assert_eq!(bytes[1], 0x30);
+        assert_eq!(
+            Board::from_bytes(&bytes).unwrap().to_string(),
+            board.to_string()
+        );
+    }
+
+    #[test]
+    fn bytes_reject_nibble_above_nine() {
+        let mut bytes = [0; 41];
+        bytes[3] = 0x0a;
+
+        assert!(matches!(
+            Board::from_bytes(&bytes),
+            Err(SudokuError::Value(10))
+        ));
+    }
+
+    fn rotate(board: &Board) -> Board {
+        Board::from_cells(array::from_fn(|row| {
+            array::from_fn(|column| board.0[8 - column][row])
+        }))
+    }
+
+    #[test]
+    fn rotated_board_is_equivalent() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+
+        assert!(board.is_equivalent(&rotate(&board)));
+        assert!(board.is_equivalent(&rotate(&rotate(&rotate(&board)))));
+    }
+
+    #[test]
+    fn relabeled_board_is_not_geometrically_equivalent() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+        let relabeled = Board::try_from(
+            input
+                .replace('1', "x")
+                .replace('2', "1")
+                .replace('x', "2")
+                .as_str(),
+        )
+        .unwrap();
+
+        assert!(board.is_geometrically_equivalent(&rotate(&board)));
+        assert!(board.is_equivalent(&relabeled));
+        assert!(!board.is_geometrically_equivalent(&relabeled));
+    }
+
+    #[test]
+    fn unrelated_board_is_not_equivalent() {
+        let board = Board::try_from(
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916",
+        )
+        .unwrap();
+        let other = Board::try_from(
+            "123456789578913624469728351245361897816297435937845216351672948792184563684539172",
+        )
+        .unwrap();
+
+        assert!(!board.is_equivalent(&other));
+    }
+
+    #[test]
+    fn default_cell_is_empty() {
+        assert_eq!(Cell::default(), Cell::Empty);
+        assert!(Board::default().iter().all(|cell| cell == Cell::Empty));
+    }
+
+    #[test]
+    fn parse_dot_as_empty_cell() {
+        let dotted =
+            "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..";
+        let zeroed =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+
+        assert_eq!(
+            Board::try_from(dotted).unwrap(),
+            Board::try_from(zeroed).unwrap()
+        );
+    }
+
+    #[test]
+    fn pretty_string_draws_box_borders() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+        let output = board.to_pretty_string();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 13);
+        assert_eq!(
+            lines
+                .iter()
+                .filter(|&&line| line == "+---+---+---+")
+                .count(),
+            4
+        );
+        assert_eq!(lines[1], "|..3|.2.|6..|");
+        assert_eq!(lines[5], "|..8|1.2|9..|");
+        assert_eq!(lines[11], "|..5|.1.|3..|");
+        assert_eq!(board.to_string().lines().count(), 9);
+    }
+
+    #[test]
+    fn read_multi_line_grid() {
+        let grid = "\
+            . . 3 | . 2 . | 6 . .\n\
+            9 . . | 3 . 5 | . . 1\n\
+            . . 1 | 8 . 6 | 4 . .\n\
+            ------+-------+------\n\
+            . . 8 | 1 . 2 | 9 . .\n\
+            7 . . | . . . | . . 8\n\
+            . . 6 | 7 . 8 | 2 . .\n\
+            ------+-------+------\n\
+            . . 2 | 6 . 9 | 5 . .\n\
+            8 . . | 2 . 3 | . . 9\n\
+            . . 5 | . 1 . | 3 . .\n";
+        let board = Board::from_reader(std::io::Cursor::new(grid)).unwrap();
+
+        assert_eq!(
+            board.to_line_string(),
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300"
+        );
+        assert!(Board::from_reader(std::io::Cursor::new("123")).is_err());
+    }
+
+    #[test]
+    fn line_string_round_trip() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+
+        assert_eq!(board.to_line_string(), input);
+        assert_eq!(
+            Board::try_from(board.to_line_string().as_str()).unwrap(),
+            board
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+        let json = serde_json::to_string(&board).unwrap();
+
+        assert_eq!(json, format!("\"{}\"", input));
+        assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+        assert_eq!(serde_json::to_string(&Cell::Value(7)).unwrap(), "7");
+        assert_eq!(serde_json::from_str::<Cell>("0").unwrap(), Cell::Empty);
+        assert!(serde_json::from_str::<Board>("\"12x\"").is_err());
+        assert!(serde_json::from_str::<Cell>("10").is_err());
+    }
+
+    #[test]
+    fn reject_letter_without_panicking() {
+        let input =
+            "x03020600900305001001806400008102900700000008006708200002609500800203009005010300";
+
+        assert!(matches!(
+            Board::try_from(input),
+            Err(SudokuError::InvalidCharacter('x'))
+        ));
+        assert!(matches!(
+            Board::try_from("003020600x"),
+            Err(SudokuError::InputLength(10))
+        ));
+    }
+
+    #[test]
+    fn reject_multibyte_character() {
+        let input =
+            "é3020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        assert_eq!(input.len(), 81);
+
+        assert!(matches!(
+            Board::try_from(input),
+            Err(SudokuError::InvalidCharacter('é'))
+        ));
+    }
+
+    #[test]
+    fn rewrite_row_through_mutable_slice() {
+        let mut board = Board::new();
+        *board.row_mut(Row::C) = [1, 2, 3, 4, 5, 6, 7, 8, 9].map(Cell::Value);
+
+        assert!(board.is_row_completed(Row::C));
+        assert_eq!(board.get_row(Row::B), [Cell::Empty; 9]);
+    }
+
+    #[test]
+    fn parse_puzzle_solution_pairs() {
+        let puzzle =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let solution =
+            "483921657967345821251876493548132976729564138136798245372689514814253769695417382";
+
+        for separator in [",", " ", ";", "\t"] {
+            let (parsed, solved) =
+                parse_pair(&format!("{}{}{}\n", puzzle, separator, solution)).unwrap();
+            assert_eq!(
+                parsed.to_string(),
+                Board::try_from(puzzle).unwrap().to_string()
+            );
+            assert_eq!(
+                solved.to_string(),
+                Board::try_from(solution).unwrap().to_string()
+            );
+        }
+
+        let other =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        assert!(matches!(
+            parse_pair(&format!("{},{}", puzzle, other)),
+            Err(SudokuError::SolutionMismatch)
+        ));
+        assert!(matches!(
+            parse_pair(puzzle),
+            Err(SudokuError::PairFormat(1))
+        ));
+        assert!(matches!(
+            parse_pair(&format!("{} {} {}", puzzle, solution, solution)),
+            Err(SudokuError::PairFormat(3))
+        ));
+    }
+
+    #[test]
+    fn linear_index_round_trip() {
+        for coordinate in [
+            (Row::A, Column::One),
+            (Row::B, Column::Four),
+            (Row::E, Column::Five),
+            (Row::I, Column::Nine),
+        ] {
+            assert_eq!(coordinate_of(index_of(coordinate)).unwrap(), coordinate);
+        }
+        assert_eq!(index_of((Row::B, Column::One)), 9);
+        assert_eq!(index_of((Row::I, Column::Nine)), 80);
+        assert!(matches!(coordinate_of(81), Err(SudokuError::Index(81))));
+    }
+
+    #[test]
+    fn apply_scripted_moves() {
+        let mut board = Board::new();
+        board.apply_moves_from_str("A1=5,B3=7, I9=9").unwrap();
+
+        assert_eq!(board.get_cell((Row::A, Column::One)), Some(Cell::Value(5)));
+        assert_eq!(
+            board.get_cell((Row::B, Column::Three)),
+            Some(Cell::Value(7))
+        );
+        assert_eq!(board.get_cell((Row::I, Column::Nine)), Some(Cell::Value(9)));
+        assert_eq!(board.clue_count(), 3);
+
+        board.apply_moves_from_str("A1=0").unwrap();
+        assert_eq!(board.get_cell((Row::A, Column::One)), Some(Cell::Empty));
+
+        assert!(matches!(
+            board.apply_moves_from_str("C2=4,J1=3"),
+            Err(SudokuError::InvalidMove(token)) if token == "J1=3"
+        ));
+        assert!(matches!(
+            board.apply_moves_from_str("C2=12"),
+            Err(SudokuError::Value(12))
+        ));
+        assert_eq!(board.get_cell((Row::C, Column::Two)), Some(Cell::Empty));
+    }
+
+    #[test]
+    fn markers_flag_conflicting_cells() {
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::One), 4).unwrap();
+        board.set_cell((Row::A, Column::Five), 4).unwrap();
+        board.set_cell((Row::B, Column::Two), 7).unwrap();
+
+        let output = board.display_with_markers();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines[0], "4*___4*____");
+        assert_eq!(lines[1], "_7_______");
+        assert_eq!(lines[2], "_________");
+        assert_eq!(lines.len(), 9);
+    }
+
+    #[test]
+    fn frozen_board_reads_and_thaws() {
+        let mut board = Board::new();
+        board.set_cell((Row::C, Column::Four), 6).unwrap();
+
+        let frozen = board.freeze();
+        assert_eq!(
+            frozen.get_cell((Row::C, Column::Four)),
+            Some(Cell::Value(6))
+        );
+        assert_eq!(frozen.clue_count(), 1);
+        assert!(frozen.is_valid());
+
+        let mut board = frozen.thaw();
+        board.set_cell((Row::C, Column::Four), 0).unwrap();
+        assert_eq!(board.clue_count(), 0);
+    }
+
+    #[test]
+    fn labeled_display() {
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::One), 1).unwrap();
+        board.set_cell((Row::E, Column::Five), 5).unwrap();
+        board.set_cell((Row::I, Column::Nine), 9).unwrap();
+
+        let expected = "  1 2 3 4 5 6 7 8 9\n\
+                        A 1 _ _ _ _ _ _ _ _\n\
+                        B _ _ _ _ _ _ _ _ _\n\
+                        C _ _ _ _ _ _ _ _ _\n\
+                        D _ _ _ _ _ _ _ _ _\n\
+                        E _ _ _ _ 5 _ _ _ _\n\
+                        F _ _ _ _ _ _ _ _ _\n\
+                        G _ _ _ _ _ _ _ _ _\n\
+                        H _ _ _ _ _ _ _ _ _\n\
+                        I _ _ _ _ _ _ _ _ 9\n";
+        assert_eq!(board.display_labeled(), expected);
+    }
+
+    fn jigsaw_regions() -> Regions {
+        Regions::new([
+            [2, 2, 2, 2, 8, 8, 6, 6, 6],
+            [5, 2, 2, 2, 8, 8, 1, 6, 6],
+            [5, 2, 2, 8, 8, 8, 1, 1, 6],
+            [5, 5, 8, 8, 0, 0, 1, 1, 6],
+            [5, 5, 5, 0, 0, 0, 1, 1, 6],
+            [5, 7, 5, 3, 0, 0, 1, 1, 6],
+            [7, 7, 7, 3, 0, 4, 4, 4, 4],
+            [7, 7, 7, 3, 0, 4, 4, 4, 4],
+            [7, 7, 3, 3, 3, 3, 3, 3, 4],
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn regions_must_have_nine_cells() {
+        let mut layout = Regions::standard().0;
+        layout[0][3] = 0;
+
+        assert!(matches!(
+            Regions::new(layout),
+            Err(SudokuError::RegionSize {
+                region: 0,
+                size: 10
+            })
+        ));
+    }
+
+    #[test]
+    fn validate_region_shapes() {
+        assert!(jigsaw_regions().validate().is_ok());
+        assert!(Regions::standard().validate().is_ok());
+
+        let mut unequal = Regions::standard();
+        unequal.0[0][3] = 0;
+        assert!(matches!(
+            unequal.validate(),
+            Err(SudokuError::RegionSize { region: 0, .. })
+        ));
+
+        let mut disconnected = Regions::standard();
+        disconnected.0[0][0] = 8;
+        disconnected.0[8][8] = 0;
+        assert!(matches!(
+            disconnected.validate(),
+            Err(SudokuError::RegionDisconnected(0))
+        ));
+    }
+
+    #[test]
+    fn jigsaw_region_completed() {
+        let regions = jigsaw_regions();
+        let mut board = Board::new();
+        let cells = [
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 1),
+            (1, 2),
+            (1, 3),
+            (2, 1),
+            (2, 2),
+        ];
+        for (num, (row, column)) in (1..=9).zip(cells) {
+            board
+                .set_cell(
+                    (
+                        Row::try_from(row).unwrap(),
+                        Column::try_from(column).unwrap(),
+                    ),
+                    num,
+                )
+                .unwrap();
+        }
+
+        assert!(board.is_region_completed(&regions, 2));
+        assert!(!board.is_region_completed(&regions, 0));
+        assert!(!board.is_region_completed(&Regions::standard(), 0));
+    }
+
+    #[test]
+    fn diagonal_duplicates_invalid_for_x_sudoku() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        let board = Board::try_from(input).unwrap();
+
+        assert!(board.is_valid_for(Variant::Standard));
+        assert!(!board.is_valid_for(Variant::XSudoku));
+        assert!(!board.all_diagonals_completed());
+    }
+
+    #[test]
+    fn knight_move_duplicates_invalid_with_anti_knight() {
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::Three), 5).unwrap();
+        board.set_cell((Row::B, Column::Five), 5).unwrap();
+
+        assert!(board.is_valid());
+        assert!(board.is_valid_with(&[]));
+        assert!(!board.is_valid_with(&[Constraint::AntiKnight]));
+
+        board.set_cell((Row::B, Column::Five), 0).unwrap();
+        board.set_cell((Row::C, Column::Six), 5).unwrap();
+        assert!(board.is_valid_with(&[Constraint::AntiKnight]));
+    }
+
+    #[test]
+    fn validate_correct_solution() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        let board = Board::try_from(input).unwrap();
+
+        assert!(board.validate_complete().is_ok());
+    }
+
+    #[test]
+    fn validate_complete_but_invalid_grid() {
+        let input =
+            "123456789912345678891234567789123456678912345567891234456789123345678912234567891";
+        let board = Board::try_from(input).unwrap();
+
+        assert!(board.all_rows_completed());
+        assert!(board.all_columns_completed());
+        assert!(matches!(
+            board.validate_complete(),
+            Err(SudokuError::UnitNotCompleted(Unit::Square(0)))
+        ));
+    }
+
+    #[test]
+    fn validate_incomplete_grid() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748910";
+        let board = Board::try_from(input).unwrap();
+
+        assert!(matches!(
+            board.validate_complete(),
+            Err(SudokuError::UnitNotCompleted(Unit::Row(Row::I)))
+        ));
+    }
+
+    #[test]
+    fn collect_moves_into_board() {
+        let moves = vec![
+            ((Row::A, Column::One), 1),
+            ((Row::E, Column::Five), 5),
+            ((Row::I, Column::Nine), 10),
+        ];
+        let board: Board = moves.iter().copied().collect();
+
+        assert_eq!(board.get_cell((Row::A, Column::One)), Some(Cell::Value(1)));
+        assert_eq!(board.get_cell((Row::E, Column::Five)), Some(Cell::Value(5)));
+        assert_eq!(board.get_cell((Row::I, Column::Nine)), Some(Cell::Empty));
+
+        assert!(matches!(
+            Board::try_from_moves(moves),
+            Err(SudokuError::Value(10))
+        ));
+        let board = Board::try_from_moves([((Row::B, Column::Two), 7)]).unwrap();
+        assert_eq!(board.get_cell((Row::B, Column::Two)), Some(Cell::Value(7)));
+    }
+
+    #[test]
+    fn extract_band_and_stack() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        let board = Board::try_from(input).unwrap();
+
+        let band = board.band(0).unwrap();
+        assert_eq!(band[0], board.get_row(Row::A));
+        assert_eq!(band[2], board.get_row(Row::C));
+
+        let stack = board.stack(2).unwrap();
+        assert_eq!(stack[0], [7, 8, 9].map(Cell::Value));
+        assert_eq!(stack[8], [9, 1, 6].map(Cell::Value));
+
+        assert!(matches!(board.band(3), Err(SudokuError::BandIndex(3))));
+        assert!(matches!(board.stack(3), Err(SudokuError::BandIndex(3))));
+    }
+
+    #[test]
+    fn format_with_options() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+
+        assert_eq!(board.format(&DisplayOptions::default()), board.to_string());
+        assert!(board.to_string().starts_with("__3_2_6__\n"));
+
+        let dotted = DisplayOptions {
+            empty_char: '.',
+            ..DisplayOptions::default()
+        };
+        assert!(board.format(&dotted).starts_with("..3.2.6..\n"));
+
+        let boxed = DisplayOptions {
+            empty_char: '.',
+            box_separators: true,
+            row_separator: true,
+        };
+        let output = board.format(&boxed);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert_eq!(lines[0], "..3|.2.|6..");
+        assert_eq!(lines[3], "---+---+---");
+        assert_eq!(lines[7], "---+---+---");
+
+        let rows_only = DisplayOptions {
+            row_separator: true,
+            ..DisplayOptions::default()
+        };
+        assert_eq!(board.format(&rows_only).lines().nth(3), Some("---------"));
+    }
+
+    #[test]
+    fn killer_cage_validity() {
+        let cages = [Cage {
+            cells: vec![(Row::A, Column::One), (Row::A, Column::Two)],
+            sum: 5,
+        }];
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::One), 1).unwrap();
+        assert!(board.is_valid_killer(&cages));
+        assert_eq!(
+            board.killer_candidates((Row::A, Column::Two), &cages),
+            CandidateSet::from_iter([4])
+        );
+
+        board.set_cell((Row::A, Column::Two), 4).unwrap();
+        assert!(board.is_valid_killer(&cages));
+        assert!(board
+            .killer_candidates((Row::A, Column::Two), &cages)
+            .is_empty());
+
+        board.set_cell((Row::A, Column::Two), 3).unwrap();
+        assert!(board.is_valid());
+        assert!(!board.is_valid_killer(&cages));
+    }
+
+    #[test]
+    fn mask_keeps_marked_clues() {
+        let solution = Board::try_from(
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916",
+        )
+        .unwrap();
+        let keep: [[bool; 9]; 9] = array::from_fn(|row| array::from_fn(|column| row == column));
+
+        let puzzle = solution.mask_clues(&keep);
+
+        assert_eq!(puzzle.clue_count(), 9);
+        for (row, column, cell) in puzzle.entries() {
+            let (r, c) = (usize::from(row), usize::from(column));
+            if r == c {
+                assert_eq!(Some(cell), solution.get_cell((row, column)));
+            } else {
+                assert_eq!(cell, Cell::Empty);
+            }
+        }
+    }
+
+    #[test]
+    fn mask_marks_changed_cells() {
+        let previous = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let mut board = previous.clone();
+        board.set_cell((Row::A, Column::One), 4).unwrap();
+
+        let mut expected = [[false; 9]; 9];
+        expected[0][0] = true;
+        assert_eq!(board.changed_mask(&previous), expected);
+        assert_eq!(previous.changed_mask(&previous), [[false; 9]; 9]);
+    }
+
+    #[test]
+    fn sdk_round_trip() {
+        let board = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let sdk = board.to_sdk();
+
+        assert_eq!(sdk.lines().count(), 9);
+        assert!(sdk.starts_with("..3.2.6..\n9..3.5..1\n"));
+        assert_eq!(
+            Board::from_sdk(&sdk).unwrap().to_string(),
+            board.to_string()
+        );
+
+        let commented = format!("#A Unknown\n#D Example\n{}", sdk);
+        assert_eq!(
+            Board::from_sdk(&commented).unwrap().to_string(),
+            board.to_string()
+        );
+    }
+
+    #[test]
+    fn html_marks_given_cells() {
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::One), 5).unwrap();
+        board.set_cell((Row::A, Column::Two), 3).unwrap();
+        let mut givens = [[false; 9]; 9];
+        givens[0][0] = true;
+
+        let html = board.to_html_with_givens(&givens);
+
+        assert!(html.contains("<tr><td class=\"given\">5</td><td class=\"entered\">3</td>"));
+        assert_eq!(html.matches("class=\"given\"").count(), 1);
+        assert_eq!(html.matches("<tr>").count(), 9);
+    }
+
+    #[test]
+    fn option_grid_round_trip() {
+        let board = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let grid = board.to_option_grid();
+
+        assert_eq!(grid[0][..3], [None, None, Some(3)]);
+        assert_eq!(
+            Board::from_option_grid(&grid).unwrap().to_string(),
+            board.to_string()
+        );
+
+        let mut invalid = grid;
+        invalid[4][4] = Some(10);
+        assert!(matches!(
+            Board::from_option_grid(&invalid),
+            Err(SudokuError::Value(10))
+        ));
+    }
+
+    #[test]
+    fn rle_round_trip() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let board = Board::try_from(input).unwrap();
+        let rle = board.to_rle();
+
+        assert!(rle.starts_with("b3a2a6b9b3a5b1"));
+        assert_eq!(
+            Board::from_rle(&rle).unwrap().to_string(),
+            board.to_string()
+        );
+        assert!(matches!(
+            Board::from_rle("9z"),
+            Err(SudokuError::InputLength(27))
+        ));
+    }
+
+    #[test]
+    fn rle_of_empty_board() {
+        let rle = Board::new().to_rle();
+
+        assert_eq!(rle, "zzzc");
+        assert!(Board::from_rle(&rle)
+            .unwrap()
+            .iter()
+            .all(|cell| cell == Cell::Empty));
+    }
+
+    #[test]
+    fn entries_are_row_major() {
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::Two), 8).unwrap();
+        let entries: Vec<(Row, Column, Cell)> = board.entries().collect();
+
+        assert_eq!(entries.len(), 81);
+        assert!(matches!(entries[0], (Row::A, Column::One, _)));
+        assert_eq!(entries[1], (Row::A, Column::Two, Cell::Value(8)));
+        assert_eq!(entries[80], (Row::I, Column::Nine, Cell::Empty));
+    }
+
+    #[test]
+    fn highlight_single_cell() {
+        let mut board = Board::new();
+        board.set_cell((Row::B, Column::Three), 7).unwrap();
+        board.set_cell((Row::B, Column::Four), 2).unwrap();
+
+        let output = board.with_highlight((Row::B, Column::Three));
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[1], " _  _ [7] 2  _  _  _  _  _ ");
+        assert_eq!(output.matches('[').count(), 1);
+        assert!(board
+            .with_highlight((Row::A, Column::One))
+            .starts_with("[_]"));
+    }
+
+    proptest! {
+        #[test]
+        fn parse_line_round_trip(input in "[0-9]{81}") {
+            let board = Board::try_from(input.as_str()).unwrap();
+            prop_assert_eq!(board.to_line_string(), input);
+        }
+
+        #[test]
+        fn valid_board_export_round_trip(seed in any::<u64>(), filled in 0usize..=40) {
+            let board = Board::random_valid(filled, &mut StdRng::seed_from_u64(seed)).unwrap();
+
+            prop_assert_eq!(Board::from_bytes(&board.to_bytes()).unwrap(), board.clone());
+            prop_assert_eq!(Board::from_rle(&board.to_rle()).unwrap(), board);
+        }
     }
 }
@@ -0,0 +1,231 @@
+use std::{collections::HashSet, fmt::Display};
+
+use crate::board::{Cell, SudokuError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Board4([[Cell; 4]; 4]);
+
+impl Board4 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_cell(&self, coordinates: (usize, usize)) -> Option<Cell> {
+        self.0
+            .get(coordinates.0)
+            .and_then(|row| row.get(coordinates.1))
+            .copied()
+    }
+
+    pub fn set_cell(&mut self, coordinates: (usize, usize), number: u8) -> Result<(), SudokuError> {
+        let (row, column) = coordinates;
+        if row >= 4 || column >= 4 {
+            return Err(SudokuError::Coordinate4 { row, column });
+        }
+        self.0[row][column] = cell_from(number)?;
+        Ok(())
+    }
+
+    pub fn get_row(&self, row: usize) -> [Cell; 4] {
+        self.0[row]
+    }
+
+    pub fn get_column(&self, column: usize) -> [Cell; 4] {
+        self.0.map(|row| row[column])
+    }
+
+    pub fn get_square(&self, square_index: usize) -> [Cell; 4] {
+        let row = (square_index / 2) * 2;
+        let column = (square_index % 2) * 2;
+        std::array::from_fn(|i| self.0[row + i / 2][column + i % 2])
+    }
+
+    pub fn is_row_completed(&self, row: usize) -> bool {
+        row < 4 && is_completed(self.get_row(row))
+    }
+
+    pub fn is_column_completed(&self, column: usize) -> bool {
+        column < 4 && is_completed(self.get_column(column))
+    }
+
+    pub fn is_square_completed(&self, square_index: usize) -> bool {
+        square_index < 4 && is_completed(self.get_square(square_index))
+    }
+
+    pub fn is_completed(&self) -> bool {
+        (0..4).all(|i| {
+            self.is_row_completed(i) && self.is_column_completed(i) && self.is_square_completed(i)
+        })
+    }
+
+    pub fn is_valid(&self) -> bool {
+        (0..4).all(|i| {
+            no_duplicates(self.get_row(i))
+                && no_duplicates(self.get_column(i))
+                && no_duplicates(self.get_square(i))
+        })
+    }
+
+    pub fn candidates(&self, coordinates: (usize, usize)) -> HashSet<u8> {
+        let (row, column) = coordinates;
+        if self.get_cell(coordinates) != Some(Cell::Empty) {
+            return HashSet::new();
+        }
+
+        let mut candidates: HashSet<u8> = (1..=4).collect();
+        self.get_row(row)
+            .iter()
+            .chain(self.get_column(column).iter())
+            .chain(self.get_square((row / 2) * 2 + column / 2).iter())
+            .for_each(|cell| {
+                if let Cell::Value(num) = cell {
+                    candidates.remove(num);
+                }
+            });
+        candidates
+    }
+
+    pub fn solve(&mut self) -> bool {
+        self.is_valid() && backtrack(self)
+    }
+}
+
+impl TryFrom<&str> for Board4 {
+    type Error = SudokuError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if let Some(c) = value.chars().find(|c| !c.is_ascii()) {
+            return Err(SudokuError::InvalidCharacter(c));
+        }
+
+        if value.len() != 16 {
+            return Err(SudokuError::InputLength4(value.len()));
+        }
+
+        let mut board = [[Cell::default(); 4]; 4];
+        for (i, c) in value.chars().enumerate() {
+            board[i / 4][i % 4] = match c {
+                '.' => Cell::Empty,
+                c => {
+                    let digit = c.to_digit(10).ok_or(SudokuError::InvalidCharacter(c))?;
+                    cell_from(digit as u8)?
+                }
+            };
+        }
+
+        Ok(Self(board))
+    }
+}
+
+impl Display for Board4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.0 {
+            for cell in row {
+                match cell {
+                    Cell::Empty => write!(f, "_")?,
+                    Cell::Value(val) => write!(f, "{}", val)?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn cell_from(number: u8) -> Result<Cell, SudokuError> {
+    match number {
+        0 => Ok(Cell::Empty),
+        1..=4 => Ok(Cell::Value(number)),
+        _ => Err(SudokuError::Value4(number)),
+    }
+}
+
+fn is_completed(cells: [Cell; 4]) -> bool {
+    let mut set = HashSet::new();
+    cells.iter().all(|cell| match cell {
+        Cell::Empty => false,
+        Cell::Value(num) => set.insert(*num),
+    })
+}
+
+fn no_duplicates(cells: [Cell; 4]) -> bool {
+    let mut set = HashSet::new();
+    cells.iter().all(|cell| match cell {
+        Cell::Empty => true,
+        Cell::Value(num) => set.insert(*num),
+    })
+}
+
+fn backtrack(board: &mut Board4) -> bool {
+    let Some(coordinates) = (0..16)
+        .map(|index| (index / 4, index % 4))
+        .find(|&coordinates| board.get_cell(coordinates) == Some(Cell::Empty))
+    else {
+        return true;
+    };
+
+    let candidates = board.candidates(coordinates);
+    for value in (1..=4).filter(|value| candidates.contains(value)) {
+        board.set_cell(coordinates, value).unwrap();
+        if backtrack(board) {
+            return true;
+        }
+    }
+
+    board.set_cell(coordinates, 0).unwrap();
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_small_puzzle() {
+        let mut board = Board4::try_from("1..4...2.1..4..1").unwrap();
+
+        assert!(board.solve());
+        assert!(board.is_completed());
+        assert_eq!(board, Board4::try_from("1234341221434321").unwrap());
+    }
+
+    #[test]
+    fn completion_checks() {
+        let board = Board4::try_from("1234341221434321").unwrap();
+        assert!((0..4).all(|i| board.is_row_completed(i)));
+        assert!((0..4).all(|i| board.is_column_completed(i)));
+        assert!((0..4).all(|i| board.is_square_completed(i)));
+
+        let board = Board4::try_from("1234123400000000").unwrap();
+        assert!(board.is_row_completed(0));
+        assert!(!board.is_column_completed(0));
+        assert!(!board.is_valid());
+    }
+
+    #[test]
+    fn reject_invalid_input() {
+        assert!(matches!(
+            Board4::try_from("1234341221434325"),
+            Err(SudokuError::Value4(5))
+        ));
+        assert!(matches!(
+            Board4::try_from("123"),
+            Err(SudokuError::InputLength4(3))
+        ));
+        assert!(matches!(
+            Board4::new().set_cell((4, 0), 1),
+            Err(SudokuError::Coordinate4 { row: 4, column: 0 })
+        ));
+        assert!(matches!(
+            Board4::new().set_cell((0, 5), 1),
+            Err(SudokuError::Coordinate4 { row: 0, column: 5 })
+        ));
+    }
+
+    #[test]
+    fn unsolvable_puzzle() {
+        let mut board = Board4::try_from("123....4........").unwrap();
+        assert!(!board.solve());
+        assert_eq!(board, Board4::try_from("123....4........").unwrap());
+    }
+}
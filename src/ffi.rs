@@ -0,0 +1,98 @@
+use std::ffi::{c_char, CStr};
+
+use crate::{board::Board, solver::Sudoku};
+
+pub const SUDOKU_OK: i32 = 0;
+pub const SUDOKU_NULL_POINTER: i32 = -1;
+pub const SUDOKU_INVALID_INPUT: i32 = -2;
+pub const SUDOKU_UNSOLVABLE: i32 = -3;
+
+/// Solves the 81-character puzzle in `input` and writes the 81-character
+/// solution followed by a NUL terminator to `output`.
+///
+/// Returns `SUDOKU_OK` on success or one of the negative error codes.
+///
+/// # Safety
+///
+/// `input` must point to a NUL-terminated string and `output` must point to
+/// a caller-owned buffer of at least 82 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_solve(input: *const c_char, output: *mut c_char) -> i32 {
+    if input.is_null() || output.is_null() {
+        return SUDOKU_NULL_POINTER;
+    }
+
+    let Ok(input) = CStr::from_ptr(input).to_str() else {
+        return SUDOKU_INVALID_INPUT;
+    };
+    let Ok(mut board) = Board::from_api_string(input) else {
+        return SUDOKU_INVALID_INPUT;
+    };
+    let Some(digits) = Sudoku::new(&mut board).solve_to_array() else {
+        return SUDOKU_UNSOLVABLE;
+    };
+
+    for (i, digit) in digits.iter().enumerate() {
+        *output.add(i) = (b'0' + digit) as c_char;
+    }
+    *output.add(81) = 0;
+    SUDOKU_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::CString, ptr};
+
+    use super::*;
+
+    #[test]
+    fn solve_through_raw_pointers() {
+        let input = CString::new(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let mut output = [0 as c_char; 82];
+
+        let code = unsafe { sudoku_solve(input.as_ptr(), output.as_mut_ptr()) };
+        let solution = unsafe { CStr::from_ptr(output.as_ptr()) };
+
+        assert_eq!(code, SUDOKU_OK);
+        assert_eq!(
+            solution.to_str().unwrap(),
+            "483921657967345821251876493548132976729564138136798245372689514814253769695417382"
+        );
+    }
+
+    #[test]
+    fn report_errors() {
+        let mut output = [0 as c_char; 82];
+        let invalid = CString::new("12x").unwrap();
+        let bad_character = CString::new(
+            "x03020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let unsolvable = CString::new(
+            "023456789000000000000000000100000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+
+        unsafe {
+            assert_eq!(
+                sudoku_solve(ptr::null(), output.as_mut_ptr()),
+                SUDOKU_NULL_POINTER
+            );
+            assert_eq!(
+                sudoku_solve(invalid.as_ptr(), output.as_mut_ptr()),
+                SUDOKU_INVALID_INPUT
+            );
+            assert_eq!(
+                sudoku_solve(bad_character.as_ptr(), output.as_mut_ptr()),
+                SUDOKU_INVALID_INPUT
+            );
+            assert_eq!(
+                sudoku_solve(unsolvable.as_ptr(), output.as_mut_ptr()),
+                SUDOKU_UNSOLVABLE
+            );
+        }
+    }
+}
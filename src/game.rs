@@ -0,0 +1,157 @@
+use crate::{
+    board::{Board, Cell, Column, Row},
+    solver::{SolveError, Sudoku},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveResult {
+    Correct,
+    Incorrect,
+    AlreadyFilled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameStatus {
+    InProgress,
+    Solved,
+    Unsolvable,
+    Broken,
+}
+
+/// A puzzle being played, together with its unique solution.
+#[derive(Debug, Clone)]
+pub struct Game {
+    pub board: Board,
+    solution: Board,
+}
+
+impl Game {
+    pub fn new(puzzle: Board) -> Result<Self, SolveError> {
+        let mut solution = puzzle.clone();
+        Sudoku::new(&mut solution).solve_strict()?;
+        Ok(Self {
+            board: puzzle,
+            solution,
+        })
+    }
+
+    pub fn check_move(&self, coordinate: (Row, Column), value: u8) -> MoveResult {
+        if self.board.get_cell(coordinate) != Some(Cell::Empty) {
+            return MoveResult::AlreadyFilled;
+        }
+        if self.is_correct(coordinate, value) {
+            MoveResult::Correct
+        } else {
+            MoveResult::Incorrect
+        }
+    }
+
+    pub fn is_correct(&self, coordinate: (Row, Column), value: u8) -> bool {
+        self.solution.get_cell(coordinate) == Some(Cell::Value(value))
+    }
+
+    /// `Broken` means the board has conflicts; `Unsolvable` means it has none
+    /// but can no longer be completed.
+    pub fn status(&self) -> GameStatus {
+        if !self.board.is_valid() {
+            GameStatus::Broken
+        } else if self.board.validate_complete().is_ok() {
+            GameStatus::Solved
+        } else if Sudoku::new(&mut self.board.clone()).solution_count(1) == 0 {
+            GameStatus::Unsolvable
+        } else {
+            GameStatus::InProgress
+        }
+    }
+
+    pub fn reveal_cell(&mut self, coordinate: (Row, Column)) -> Option<u8> {
+        if self.board.get_cell(coordinate) != Some(Cell::Empty) {
+            return None;
+        }
+        let Some(Cell::Value(value)) = self.solution.get_cell(coordinate) else {
+            return None;
+        };
+        self.board.set_cell(coordinate, value).ok()?;
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str =
+        "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+
+    fn game() -> Game {
+        Game::new(Board::try_from(PUZZLE).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn check_move_outcomes() {
+        let game = game();
+
+        assert_eq!(
+            game.check_move((Row::A, Column::One), 4),
+            MoveResult::Correct
+        );
+        assert_eq!(
+            game.check_move((Row::A, Column::One), 5),
+            MoveResult::Incorrect
+        );
+        assert_eq!(
+            game.check_move((Row::A, Column::Three), 3),
+            MoveResult::AlreadyFilled
+        );
+    }
+
+    #[test]
+    fn revealed_cell_matches_solution() {
+        let mut game = game();
+
+        assert_eq!(game.reveal_cell((Row::A, Column::One)), Some(4));
+        assert_eq!(
+            game.board.get_cell((Row::A, Column::One)),
+            game.solution.get_cell((Row::A, Column::One))
+        );
+        assert_eq!(game.reveal_cell((Row::A, Column::One)), None);
+        assert_eq!(game.reveal_cell((Row::A, Column::Three)), None);
+    }
+
+    #[test]
+    fn status_follows_the_board() {
+        let mut game = game();
+        assert_eq!(game.status(), GameStatus::InProgress);
+
+        let wrong = game
+            .board
+            .candidates((Row::A, Column::One))
+            .into_iter()
+            .find(|&value| value != 4)
+            .unwrap();
+        game.board.set_cell((Row::A, Column::One), wrong).unwrap();
+        assert_eq!(game.status(), GameStatus::Unsolvable);
+
+        game.board.set_cell((Row::A, Column::One), 3).unwrap();
+        assert_eq!(game.status(), GameStatus::Broken);
+
+        game.board = game.solution.clone();
+        assert_eq!(game.status(), GameStatus::Solved);
+    }
+
+    #[test]
+    fn correct_and_incorrect_guesses() {
+        let game = game();
+
+        assert!(game.is_correct((Row::A, Column::One), 4));
+        assert!(!game.is_correct((Row::A, Column::One), 8));
+    }
+
+    #[test]
+    fn new_rejects_puzzles_without_unique_solution() {
+        assert_eq!(
+            Game::new(Board::new()).unwrap_err(),
+            SolveError::MultipleSolutions
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod board;
+pub mod board4;
+pub mod ffi;
+pub mod game;
+pub mod solver;
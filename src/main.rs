@@ -1,11 +1,20 @@
-mod board;
-mod solver;
+use std::fs::File;
 
-use board::Board;
+use anyhow::Context;
+use sudoku::board::Board;
 
 fn main() -> anyhow::Result<()> {
-    let input = "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
-    let board = Board::try_from(input)?;
+    let board = match std::env::args().nth(1) {
+        Some(path) => {
+            let file = File::open(&path).with_context(|| format!("failed to open {}", path))?;
+            Board::from_reader(file).with_context(|| format!("failed to read {}", path))?
+        }
+        None => {
+            let input =
+                "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+            Board::try_from(input)?
+        }
+    };
 
     println!("{}", board);
 
@@ -1,12 +1,2500 @@
-use crate::board::Board;
+use std::{
+    array,
+    collections::{HashMap, HashSet},
+    fs,
+    ops::ControlFlow,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Context};
+use rand::{seq::SliceRandom, Rng};
+
+use crate::board::{
+    coordinate_of, index_of, Board, Cage, CandidateGrid, CandidateSet, Cell, Column, Constraint,
+    Regions, Row, Unit, Variant,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strategy {
+    NakedSingle,
+    HiddenSingle,
+    Guess,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SolveStats {
+    pub solved: bool,
+    pub nodes: usize,
+    pub backtracks: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveOutcome {
+    Solved,
+    TimedOut,
+    Unsolvable,
+}
+
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum SolveError {
+    #[error("puzzle has no solution")]
+    NoSolution,
+    #[error("puzzle has more than one solution")]
+    MultipleSolutions,
+    #[error("puzzle has {0} clues but a unique puzzle needs at least 17")]
+    TooFewClues(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hint {
+    pub coordinate: (Row, Column),
+    pub value: u8,
+    pub strategy: Strategy,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elimination {
+    pub coordinate: (Row, Column),
+    pub digit: u8,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveStep {
+    NakedSingle {
+        coordinate: (Row, Column),
+        value: u8,
+    },
+    HiddenSingle {
+        coordinate: (Row, Column),
+        value: u8,
+        unit: Unit,
+    },
+    Guess {
+        coordinate: (Row, Column),
+        value: u8,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PuzzleRecord {
+    pub puzzle: Board,
+    pub solution: Board,
+    pub difficulty: Difficulty,
+    pub clue_count: usize,
+}
+
+impl PuzzleRecord {
+    pub fn from_puzzle(mut puzzle: Board) -> Result<Self, SolveError> {
+        let difficulty = Sudoku::new(&mut puzzle).difficulty();
+
+        let mut solution = puzzle.clone();
+        Sudoku::new(&mut solution).solve_strict()?;
+        Ok(Self {
+            clue_count: puzzle.clue_count(),
+            puzzle,
+            solution,
+            difficulty,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BatchReport {
+    pub solutions: Vec<(usize, Board)>,
+    pub timed_out: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub enum LogicalResult {
+    SolvedLogically,
+    RequiredGuessing(Box<Board>),
+    Unsolvable,
+}
 
 #[derive(Debug)]
 pub struct Sudoku<'a> {
-    sudoku: &'a Board,
+    sudoku: &'a mut Board,
 }
 
 impl<'a> Sudoku<'a> {
     pub fn new(sudoku: &'a mut Board) -> Self {
         Self { sudoku }
     }
+
+    pub fn solve(&mut self) -> Option<Board> {
+        if !self.sudoku.is_valid() || !backtrack(self.sudoku) {
+            return None;
+        }
+        Some(self.sudoku.clone())
+    }
+
+    pub fn solution_count(&self, cap: usize) -> usize {
+        if cap == 0 {
+            return 0;
+        }
+        count_solutions(self.sudoku, cap)
+    }
+
+    pub fn solve_with_trace(&self) -> Option<(Board, Vec<SolveStep>)> {
+        if !self.sudoku.is_valid() {
+            return None;
+        }
+
+        let mut board = self.sudoku.clone();
+        let mut steps = Vec::new();
+        solve_traced(&mut board, &mut steps).then_some((board, steps))
+    }
+
+    pub fn solve_with_stats(&mut self) -> SolveStats {
+        let mut stats = SolveStats::default();
+        stats.solved = self.sudoku.is_valid() && solve_counting(self.sudoku, &mut stats);
+        stats
+    }
+
+    pub fn solve_with_timeout(&mut self, budget: Duration) -> SolveOutcome {
+        if !self.sudoku.is_valid() {
+            return SolveOutcome::Unsolvable;
+        }
+
+        let original = self.sudoku.clone();
+        let deadline = Instant::now() + budget;
+        match solve_until(self.sudoku, deadline) {
+            Some(true) => SolveOutcome::Solved,
+            Some(false) => SolveOutcome::Unsolvable,
+            None => {
+                *self.sudoku = original;
+                SolveOutcome::TimedOut
+            }
+        }
+    }
+
+    /// Fills the board with its lexicographically smallest solution in
+    /// row-major order, returning false if there is none.
+    pub fn solve_deterministic(&mut self) -> bool {
+        if count_solutions(self.sudoku, 1) == 0 {
+            return false;
+        }
+
+        let empty: Vec<(Row, Column)> = self.sudoku.empty_cells().collect();
+        for coordinates in empty {
+            let value = (1..=9)
+                .filter(|value| self.sudoku.candidates(coordinates).contains(value))
+                .find(|&value| {
+                    let mut board = self.sudoku.clone();
+                    board.set_cell(coordinates, value).unwrap();
+                    count_solutions(&board, 1) > 0
+                })
+                .unwrap();
+            self.sudoku.set_cell(coordinates, value).unwrap();
+        }
+        true
+    }
+
+    pub fn solve_strict(&mut self) -> Result<(), SolveError> {
+        match count_solutions(self.sudoku, 2) {
+            0 => Err(SolveError::NoSolution),
+            1 => {
+                *self.sudoku = first_solution(self.sudoku, &Board::candidate_set)
+                    .ok_or(SolveError::NoSolution)?;
+                Ok(())
+            }
+            _ => Err(SolveError::MultipleSolutions),
+        }
+    }
+
+    pub fn validate_clue_count(&self) -> Result<(), SolveError> {
+        match self.sudoku.clue_count() {
+            count if count < 17 => Err(SolveError::TooFewClues(count)),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn solve_logically(&mut self) -> LogicalResult {
+        if !self.sudoku.is_valid() {
+            return LogicalResult::Unsolvable;
+        }
+
+        let mut board = self.sudoku.clone();
+        propagate_singles(&mut board);
+        if !board.is_valid() {
+            return LogicalResult::Unsolvable;
+        }
+        if board.empty_cells().next().is_none() {
+            *self.sudoku = board;
+            return LogicalResult::SolvedLogically;
+        }
+
+        match first_solution(&board, &Board::candidate_set) {
+            Some(solution) => {
+                *self.sudoku = solution;
+                LogicalResult::RequiredGuessing(Box::new(board))
+            }
+            None => LogicalResult::Unsolvable,
+        }
+    }
+
+    pub fn solve_randomized(&mut self, rng: &mut impl Rng) -> bool {
+        self.sudoku.is_valid() && solve_shuffled(self.sudoku, rng)
+    }
+
+    pub fn solve_unit_first(&mut self, unit: Unit) -> bool {
+        if !self.sudoku.is_valid() {
+            return false;
+        }
+
+        let targets = Board::unit_coordinates(unit);
+        loop {
+            let Some((coordinates, value)) = naked_singles(self.sudoku)
+                .chain(hidden_singles(self.sudoku))
+                .find(|(coordinates, _)| targets.contains(coordinates))
+            else {
+                break;
+            };
+            self.sudoku.set_cell(coordinates, value).unwrap();
+        }
+
+        match first_solution(self.sudoku, &Board::candidate_set) {
+            Some(solution) => {
+                *self.sudoku = solution;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn solve_to_array(&self) -> Option<[u8; 81]> {
+        if !self.sudoku.is_valid() {
+            return None;
+        }
+
+        let solution = first_solution(self.sudoku, &Board::candidate_set)?;
+        let mut digits = [0; 81];
+        for (digit, cell) in digits.iter_mut().zip(solution.iter()) {
+            if let Cell::Value(num) = cell {
+                *digit = num;
+            }
+        }
+        Some(digits)
+    }
+
+    pub fn for_each_solution<F: FnMut(&Board) -> ControlFlow<()>>(&self, mut f: F) {
+        if !self.sudoku.is_valid() {
+            return;
+        }
+
+        let mut board = self.sudoku.clone();
+        let _ = search(&mut board, &Board::candidate_set, &mut f);
+    }
+
+    pub fn solution_iter(&self) -> impl Iterator<Item = Board> {
+        let stack = if self.sudoku.is_valid() {
+            vec![self.sudoku.clone()]
+        } else {
+            Vec::new()
+        };
+        Solutions { stack }
+    }
+
+    pub fn forced_cells(&self, limit: usize) -> [[Option<u8>; 9]; 9] {
+        let mut solutions = self.solution_iter().take(limit);
+        let Some(first) = solutions.next() else {
+            return [[None; 9]; 9];
+        };
+
+        let mut forced = array::from_fn(|row| {
+            array::from_fn(|column| {
+                let coordinates = (
+                    Row::try_from(row).unwrap(),
+                    Column::try_from(column).unwrap(),
+                );
+                match first.get_cell(coordinates) {
+                    Some(Cell::Value(value)) => Some(value),
+                    _ => None,
+                }
+            })
+        });
+        for solution in solutions {
+            for (row, column) in coordinates() {
+                let cell = &mut forced[usize::from(row)][usize::from(column)];
+                if cell.map(Cell::Value) != solution.get_cell((row, column)) {
+                    *cell = None;
+                }
+            }
+        }
+        forced
+    }
+
+    pub fn solve_variant(&self, variant: Variant) -> Option<Board> {
+        if !self.sudoku.is_valid_for(variant) {
+            return None;
+        }
+
+        first_solution(self.sudoku, &|board: &Board, coordinates| {
+            board.variant_candidates(coordinates, variant)
+        })
+    }
+
+    pub fn solve_with_constraints(&self, constraints: &[Constraint]) -> Option<Board> {
+        if !self.sudoku.is_valid_with(constraints) {
+            return None;
+        }
+
+        first_solution(self.sudoku, &|board: &Board, coordinates| {
+            board.constrained_candidates(coordinates, constraints)
+        })
+    }
+
+    pub fn solve_killer(&self, cages: &[Cage]) -> Option<Board> {
+        if !self.sudoku.is_valid_killer(cages) {
+            return None;
+        }
+
+        first_solution(self.sudoku, &|board: &Board, coordinates| {
+            board.killer_candidates(coordinates, cages)
+        })
+    }
+
+    pub fn solve_jigsaw(&self, regions: &Regions) -> Option<Board> {
+        if !self.sudoku.is_valid_jigsaw(regions) {
+            return None;
+        }
+
+        first_solution(self.sudoku, &|board: &Board, coordinates| {
+            board.region_candidates(coordinates, regions)
+        })
+    }
+
+    pub fn redundant_clues(&self) -> Vec<(Row, Column)> {
+        if count_solutions(self.sudoku, 2) != 1 {
+            return Vec::new();
+        }
+
+        coordinates()
+            .filter(|&coordinates| self.sudoku.get_cell(coordinates) != Some(Cell::Empty))
+            .filter(|&coordinates| {
+                let mut board = self.sudoku.clone();
+                board.set_cell(coordinates, 0).unwrap();
+                count_solutions(&board, 2) == 1
+            })
+            .collect()
+    }
+
+    pub fn redundant_pairs(&self) -> Vec<((Row, Column), (Row, Column))> {
+        let redundant = self.redundant_clues();
+        let mut pairs = Vec::new();
+        for (i, &first) in redundant.iter().enumerate() {
+            for &second in &redundant[i + 1..] {
+                let mut board = self.sudoku.clone();
+                board.set_cell(first, 0).unwrap();
+                board.set_cell(second, 0).unwrap();
+                if count_solutions(&board, 2) == 1 {
+                    pairs.push((first, second));
+                }
+            }
+        }
+        pairs
+    }
+
+    pub fn nearest_solvable(&self) -> Option<(Row, Column, u8)> {
+        if count_solutions(self.sudoku, 2) == 1 {
+            return None;
+        }
+
+        coordinates().find_map(|coordinates| {
+            let Some(Cell::Value(current)) = self.sudoku.get_cell(coordinates) else {
+                return None;
+            };
+            (1..=9).filter(|&value| value != current).find_map(|value| {
+                let mut board = self.sudoku.clone();
+                board.set_cell(coordinates, value).unwrap();
+                (count_solutions(&board, 2) == 1).then_some((coordinates.0, coordinates.1, value))
+            })
+        })
+    }
+
+    pub fn implications(&self, coordinate: (Row, Column), value: u8) -> Vec<((Row, Column), u8)> {
+        if !self.sudoku.candidates(coordinate).contains(&value) {
+            return Vec::new();
+        }
+
+        let mut board = self.sudoku.clone();
+        board.set_cell(coordinate, value).unwrap();
+        propagate_singles(&mut board)
+    }
+
+    pub fn safe_candidates(&self, coordinate: (Row, Column)) -> CandidateSet {
+        self.sudoku
+            .candidate_set(coordinate)
+            .iter()
+            .filter(|&value| {
+                let mut board = self.sudoku.clone();
+                board.set_cell(coordinate, value).unwrap();
+                coordinates().all(|peer| {
+                    board.get_cell(peer) != Some(Cell::Empty)
+                        || !board.candidate_set(peer).is_empty()
+                })
+            })
+            .collect()
+    }
+
+    pub fn fill_naked_singles_listed(&mut self) -> Vec<((Row, Column), u8)> {
+        let mut placements = Vec::new();
+        while let Some((coordinates, value)) = naked_single(self.sudoku) {
+            self.sudoku.set_cell(coordinates, value).unwrap();
+            placements.push((coordinates, value));
+        }
+        placements
+    }
+
+    pub fn solve_snapshots(&self) -> Vec<Board> {
+        let mut board = self.sudoku.clone();
+        let mut snapshots = vec![board.clone()];
+        if !board.is_valid() {
+            return snapshots;
+        }
+
+        while let Some((coordinates, value)) =
+            naked_single(&board).or_else(|| hidden_single(&board))
+        {
+            board.set_cell(coordinates, value).unwrap();
+            snapshots.push(board.clone());
+        }
+
+        if board.empty_cells().next().is_some() {
+            snapshots.extend(first_solution(&board, &Board::candidate_set));
+        }
+        snapshots
+    }
+
+    pub fn easiest_next(&self) -> Option<Hint> {
+        if let Some((coordinate, value)) = naked_single(self.sudoku) {
+            return Some(Hint {
+                coordinate,
+                value,
+                strategy: Strategy::NakedSingle,
+                reason: format!(
+                    "{} has {} as its only candidate",
+                    cell_name(coordinate),
+                    value
+                ),
+            });
+        }
+
+        let squares = (0..9).map(Unit::Square);
+        let rows = (0..9).map(|i| Unit::Row(Row::try_from(i).unwrap()));
+        let columns = (0..9).map(|i| Unit::Column(Column::try_from(i).unwrap()));
+        squares.chain(rows).chain(columns).find_map(|unit| {
+            let (coordinate, value) = hidden_single_in(self.sudoku, unit)?;
+            let unit = match unit {
+                Unit::Row(row) => format!("row {:?}", row),
+                Unit::Column(column) => format!("column {}", usize::from(column) + 1),
+                Unit::Square(index) => format!("box {}", index + 1),
+            };
+            Some(Hint {
+                coordinate,
+                value,
+                strategy: Strategy::HiddenSingle,
+                reason: format!(
+                    "{} is the only place for {} in {}",
+                    cell_name(coordinate),
+                    value,
+                    unit
+                ),
+            })
+        })
+    }
+
+    pub fn trivially_forced(&self) -> Vec<((Row, Column), u8)> {
+        let mut forced: Vec<((Row, Column), u8)> = naked_singles(self.sudoku)
+            .chain(hidden_singles(self.sudoku))
+            .collect();
+        forced.sort_by_key(|&((row, column), _)| (usize::from(row), usize::from(column)));
+        forced.dedup();
+        forced
+    }
+
+    /// Easy puzzles fall to naked singles, medium ones to naked and hidden
+    /// singles; anything else is hard.
+    pub fn difficulty(&self) -> Difficulty {
+        if self.is_solvable_by(Strategy::NakedSingle) {
+            Difficulty::Easy
+        } else if self.longest_single_chain() == self.sudoku.empty_cells().count() {
+            Difficulty::Medium
+        } else {
+            Difficulty::Hard
+        }
+    }
+
+    pub fn longest_single_chain(&self) -> usize {
+        propagate_singles(&mut self.sudoku.clone()).len()
+    }
+
+    pub fn logically_determined_count(&self) -> usize {
+        if !self.sudoku.is_valid() {
+            return 0;
+        }
+        self.longest_single_chain()
+    }
+
+    pub fn max_guess_depth(&self) -> usize {
+        let mut depth = 0;
+        if self.sudoku.is_valid() {
+            guess_depth(&mut self.sudoku.clone(), 0, &mut depth);
+        }
+        depth
+    }
+
+    pub fn is_solvable_by(&self, strategy: Strategy) -> bool {
+        if !self.sudoku.is_valid() {
+            return false;
+        }
+
+        let mut board = self.sudoku.clone();
+        let next = match strategy {
+            Strategy::NakedSingle => naked_single,
+            Strategy::HiddenSingle => hidden_single,
+            Strategy::Guess => return count_solutions(&board, 1) > 0,
+        };
+        while let Some((coordinates, value)) = next(&board) {
+            board.set_cell(coordinates, value).unwrap();
+        }
+        board.validate_complete().is_ok()
+    }
+
+    /// Removes the digits of every naked pair from the rest of its unit,
+    /// logging each removal and returning how many there were.
+    pub fn apply_naked_pairs(
+        &mut self,
+        candidates: &mut CandidateGrid,
+        log: &mut Vec<Elimination>,
+    ) -> usize {
+        let logged = log.len();
+        for unit in units() {
+            let pairs: Vec<(Row, Column)> = unit
+                .into_iter()
+                .filter(|&coordinates| self.sudoku.get_cell(coordinates) == Some(Cell::Empty))
+                .filter(|&coordinates| candidates.get(coordinates).len() == 2)
+                .collect();
+            for (i, &a) in pairs.iter().enumerate() {
+                for &b in &pairs[i + 1..] {
+                    let pair = candidates.get(a);
+                    if candidates.get(b) != pair {
+                        continue;
+                    }
+
+                    let digits: String = pair.iter().map(|digit| digit.to_string()).collect();
+                    let reason = format!(
+                        "naked pair {} at {} and {}",
+                        digits,
+                        cell_name(a),
+                        cell_name(b)
+                    );
+                    for coordinate in unit.into_iter().filter(|&cell| cell != a && cell != b) {
+                        for digit in pair.iter() {
+                            if candidates.remove(coordinate, digit) {
+                                log.push(Elimination {
+                                    coordinate,
+                                    digit,
+                                    reason: reason.clone(),
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        log.len() - logged
+    }
+
+    /// Two-colors each chain of conjugate pairs for `digit` and removes it
+    /// from cells that see both colors of a chain, returning the removals.
+    pub fn apply_coloring(
+        &mut self,
+        candidates: &mut CandidateGrid,
+        digit: u8,
+        log: &mut Vec<Elimination>,
+    ) -> usize {
+        let mut links = vec![Vec::new(); 81];
+        for unit in units() {
+            let cells: Vec<usize> = unit
+                .into_iter()
+                .filter(|&coordinates| self.sudoku.get_cell(coordinates) == Some(Cell::Empty))
+                .filter(|&coordinates| candidates.get(coordinates).contains(digit))
+                .map(index_of)
+                .collect();
+            if let [a, b] = cells[..] {
+                links[a].push(b);
+                links[b].push(a);
+            }
+        }
+
+        let mut colors: [Option<bool>; 81] = [None; 81];
+        let logged = log.len();
+        for start in 0..81 {
+            if links[start].is_empty() || colors[start].is_some() {
+                continue;
+            }
+
+            let mut chain = Vec::new();
+            let mut stack = vec![(start, false)];
+            colors[start] = Some(false);
+            while let Some((cell, color)) = stack.pop() {
+                chain.push((coordinate_of(cell).unwrap(), color));
+                for &next in &links[cell] {
+                    if colors[next].is_none() {
+                        colors[next] = Some(!color);
+                        stack.push((next, !color));
+                    }
+                }
+            }
+
+            for target in coordinates() {
+                if chain.iter().any(|&(cell, _)| cell == target) {
+                    continue;
+                }
+                let sees_color = |color: bool| {
+                    chain
+                        .iter()
+                        .any(|&(cell, c)| c == color && sees(cell, target))
+                };
+                if sees_color(false) && sees_color(true) && candidates.remove(target, digit) {
+                    log.push(Elimination {
+                        coordinate: target,
+                        digit,
+                        reason: format!(
+                            "sees both colors of the {} chain through {}",
+                            digit,
+                            cell_name(chain[0].0)
+                        ),
+                    });
+                }
+            }
+        }
+        log.len() - logged
+    }
+
+    pub fn find_unique_rectangles(&self, candidates: &CandidateGrid) -> Vec<[(Row, Column); 4]> {
+        let mut rectangles = Vec::new();
+        for (top, bottom) in index_pairs() {
+            for (left, right) in index_pairs() {
+                let corners = [(top, left), (top, right), (bottom, left), (bottom, right)];
+                let boxes: HashSet<(usize, usize)> = corners
+                    .iter()
+                    .map(|&(row, column)| (row / 3, column / 3))
+                    .collect();
+                if boxes.len() != 2 {
+                    continue;
+                }
+
+                let corners = corners.map(|(row, column)| {
+                    (
+                        Row::try_from(row).unwrap(),
+                        Column::try_from(column).unwrap(),
+                    )
+                });
+                if corners
+                    .iter()
+                    .any(|&coordinates| self.sudoku.get_cell(coordinates) != Some(Cell::Empty))
+                {
+                    continue;
+                }
+                let candidates = corners.map(|coordinates| candidates.get(coordinates));
+                let shared = candidates
+                    .iter()
+                    .fold(CandidateSet::all(), |shared, &values| {
+                        shared.intersection(values)
+                    });
+                let bivalue = candidates
+                    .iter()
+                    .filter(|&&values| values.len() == 2 && values.intersection(shared) == values)
+                    .count();
+                if shared.len() >= 2 && bivalue == 3 {
+                    rectangles.push(corners);
+                }
+            }
+        }
+        rectangles
+    }
+
+    pub fn is_logically_solvable_within(&self, max_steps: usize) -> bool {
+        if !self.sudoku.is_valid() {
+            return false;
+        }
+
+        let mut board = self.sudoku.clone();
+        for _ in 0..max_steps {
+            match naked_single(&board).or_else(|| hidden_single(&board)) {
+                Some((coordinates, value)) => board.set_cell(coordinates, value).unwrap(),
+                None => break,
+            }
+        }
+        board.validate_complete().is_ok()
+    }
+
+    pub fn technique_usage(&self) -> HashMap<Strategy, usize> {
+        let mut usage = HashMap::new();
+        let mut board = self.sudoku.clone();
+
+        loop {
+            let (strategy, (coordinates, value)) = if let Some(single) = naked_single(&board) {
+                (Strategy::NakedSingle, single)
+            } else if let Some(single) = hidden_single(&board) {
+                (Strategy::HiddenSingle, single)
+            } else {
+                break;
+            };
+            board.set_cell(coordinates, value).unwrap();
+            *usage.entry(strategy).or_insert(0) += 1;
+        }
+
+        let remaining = board.empty_cells().count();
+        if remaining > 0 {
+            usage.insert(Strategy::Guess, remaining);
+        }
+
+        usage
+    }
+}
+
+struct Solutions {
+    stack: Vec<Board>,
+}
+
+impl Iterator for Solutions {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Board> {
+        while let Some(board) = self.stack.pop() {
+            let Some((coordinates, values)) = most_constrained(&board, &Board::candidate_set)
+            else {
+                return Some(board);
+            };
+
+            for value in (1..=9).rev().filter(|&value| values.contains(value)) {
+                let mut next = board.clone();
+                next.set_cell(coordinates, value).unwrap();
+                self.stack.push(next);
+            }
+        }
+        None
+    }
+}
+
+pub fn solve_file<P: AsRef<Path>>(path: P) -> anyhow::Result<Vec<Board>> {
+    let contents = fs::read_to_string(path)?;
+    let mut solutions = Vec::new();
+
+    for puzzle in puzzle_lines(&contents) {
+        let (line_number, board) = puzzle?;
+        let solution = board
+            .is_valid()
+            .then(|| first_solution(&board, &Board::candidate_set))
+            .flatten();
+        match solution {
+            Some(solution) => solutions.push(solution),
+            None => bail!("puzzle on line {} has no solution", line_number),
+        }
+    }
+
+    Ok(solutions)
+}
+
+pub fn solve_file_bounded<P: AsRef<Path>>(
+    path: P,
+    per_puzzle: Duration,
+) -> anyhow::Result<BatchReport> {
+    let contents = fs::read_to_string(path)?;
+    let mut report = BatchReport::default();
+
+    for puzzle in puzzle_lines(&contents) {
+        let (line_number, mut board) = puzzle?;
+        match Sudoku::new(&mut board).solve_with_timeout(per_puzzle) {
+            SolveOutcome::Solved => report.solutions.push((line_number, board)),
+            SolveOutcome::TimedOut => report.timed_out.push(line_number),
+            SolveOutcome::Unsolvable => bail!("puzzle on line {} has no solution", line_number),
+        }
+    }
+
+    Ok(report)
+}
+
+fn puzzle_lines(contents: &str) -> impl Iterator<Item = anyhow::Result<(usize, Board)>> + '_ {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let line_number = index + 1;
+            let board = Board::from_api_string(line)
+                .with_context(|| format!("invalid puzzle on line {}", line_number))?;
+            Ok((line_number, board))
+        })
+}
+
+fn coordinates() -> impl Iterator<Item = (Row, Column)> {
+    (0..81).map(|index| {
+        (
+            Row::try_from(index / 9).unwrap(),
+            Column::try_from(index % 9).unwrap(),
+        )
+    })
+}
+
+fn index_pairs() -> impl Iterator<Item = (usize, usize)> {
+    (0..9).flat_map(|first| (first + 1..9).map(move |second| (first, second)))
+}
+
+fn cell_name((row, column): (Row, Column)) -> String {
+    format!("{:?}{}", row, usize::from(column) + 1)
+}
+
+fn sees(a: (Row, Column), b: (Row, Column)) -> bool {
+    let square = |(row, column): (Row, Column)| (usize::from(row) / 3, usize::from(column) / 3);
+    a != b && (a.0 == b.0 || a.1 == b.1 || square(a) == square(b))
+}
+
+fn units() -> Vec<[(Row, Column); 9]> {
+    (0..9)
+        .flat_map(|i| {
+            let row: [(Row, Column); 9] =
+                array::from_fn(|j| (Row::try_from(i).unwrap(), Column::try_from(j).unwrap()));
+            let column: [(Row, Column); 9] =
+                array::from_fn(|j| (Row::try_from(j).unwrap(), Column::try_from(i).unwrap()));
+            let square: [(Row, Column); 9] = array::from_fn(|j| {
+                (
+                    Row::try_from((i / 3) * 3 + j / 3).unwrap(),
+                    Column::try_from((i % 3) * 3 + j % 3).unwrap(),
+                )
+            });
+            [row, column, square]
+        })
+        .collect()
+}
+
+fn naked_singles(board: &Board) -> impl Iterator<Item = ((Row, Column), u8)> + '_ {
+    coordinates().filter_map(|coordinates| {
+        let candidates = board.candidate_set(coordinates);
+        match candidates.len() {
+            1 => candidates.iter().next().map(|value| (coordinates, value)),
+            _ => None,
+        }
+    })
+}
+
+fn hidden_singles(board: &Board) -> impl Iterator<Item = ((Row, Column), u8)> + '_ {
+    units().into_iter().flat_map(|unit| {
+        let candidates = unit.map(|coordinates| board.candidate_set(coordinates));
+        (1..=9).filter_map(move |value| {
+            let mut positions = (0..9).filter(|&i| candidates[i].contains(value));
+            match (positions.next(), positions.next()) {
+                (Some(i), None) => Some((unit[i], value)),
+                _ => None,
+            }
+        })
+    })
+}
+
+fn hidden_single_in(board: &Board, unit: Unit) -> Option<((Row, Column), u8)> {
+    let coordinates = Board::unit_coordinates(unit);
+    let candidates = coordinates.map(|coordinates| board.candidate_set(coordinates));
+    (1..=9).find_map(|value| {
+        let mut positions = (0..9).filter(|&i| candidates[i].contains(value));
+        match (positions.next(), positions.next()) {
+            (Some(i), None) => Some((coordinates[i], value)),
+            _ => None,
+        }
+    })
+}
+
+fn naked_single(board: &Board) -> Option<((Row, Column), u8)> {
+    naked_singles(board).next()
+}
+
+fn hidden_single(board: &Board) -> Option<((Row, Column), u8)> {
+    hidden_singles(board).next()
+}
+
+fn propagate_singles(board: &mut Board) -> Vec<((Row, Column), u8)> {
+    let mut placements = Vec::new();
+    while let Some((coordinates, value)) = naked_single(board).or_else(|| hidden_single(board)) {
+        board.set_cell(coordinates, value).unwrap();
+        placements.push((coordinates, value));
+    }
+    placements
+}
+
+fn backtrack(board: &mut Board) -> bool {
+    let Some(coordinates) = board.empty_cells().next() else {
+        return true;
+    };
+
+    for value in board.candidate_set(coordinates).iter() {
+        board.set_cell(coordinates, value).unwrap();
+        if backtrack(board) {
+            return true;
+        }
+    }
+
+    board.set_cell(coordinates, 0).unwrap();
+    false
+}
+
+fn solve_traced(board: &mut Board, steps: &mut Vec<SolveStep>) -> bool {
+    let start = steps.len();
+    loop {
+        if let Some((coordinate, value)) = naked_single(board) {
+            board.set_cell(coordinate, value).unwrap();
+            steps.push(SolveStep::NakedSingle { coordinate, value });
+        } else if let Some(((coordinate, value), unit)) =
+            Unit::all().find_map(|unit| Some((hidden_single_in(board, unit)?, unit)))
+        {
+            board.set_cell(coordinate, value).unwrap();
+            steps.push(SolveStep::HiddenSingle {
+                coordinate,
+                value,
+                unit,
+            });
+        } else {
+            break;
+        }
+    }
+
+    let Some((coordinate, values)) = most_constrained(board, &Board::candidate_set) else {
+        return true;
+    };
+
+    for value in values.iter() {
+        let mut next = board.clone();
+        next.set_cell(coordinate, value).unwrap();
+        let guess = steps.len();
+        steps.push(SolveStep::Guess { coordinate, value });
+        if solve_traced(&mut next, steps) {
+            *board = next;
+            return true;
+        }
+        steps.truncate(guess);
+    }
+
+    steps.truncate(start);
+    false
+}
+
+fn guess_depth(board: &mut Board, depth: usize, max: &mut usize) -> bool {
+    propagate_singles(board);
+    if !board.is_valid() {
+        return false;
+    }
+
+    *max = (*max).max(depth);
+    let Some((coordinates, values)) = most_constrained(board, &Board::candidate_set) else {
+        return true;
+    };
+
+    values.iter().any(|value| {
+        let mut next = board.clone();
+        next.set_cell(coordinates, value).unwrap();
+        guess_depth(&mut next, depth + 1, max)
+    })
+}
+
+fn first_solution<C>(board: &Board, candidates: &C) -> Option<Board>
+where
+    C: Fn(&Board, (Row, Column)) -> CandidateSet,
+{
+    let mut solution = None;
+    let mut board = board.clone();
+    let _ = search(&mut board, candidates, &mut |board: &Board| {
+        solution = Some(board.clone());
+        ControlFlow::Break(())
+    });
+    solution
+}
+
+fn count_solutions(board: &Board, cap: usize) -> usize {
+    if !board.is_valid() {
+        return 0;
+    }
+
+    let mut count = 0;
+    let mut board = board.clone();
+    let _ = search(&mut board, &Board::candidate_set, &mut |_: &Board| {
+        count += 1;
+        if count >= cap {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    count
+}
+
+const GENERATE_ATTEMPTS: usize = 100;
+
+impl Board {
+    pub fn derive_puzzles(solution: &Board, count: usize, rng: &mut impl Rng) -> Vec<Board> {
+        let mut puzzles = Vec::new();
+        let mut seen = HashSet::new();
+        let mut positions: Vec<(Row, Column)> = coordinates().collect();
+
+        for _ in 0..count * 4 {
+            if puzzles.len() == count {
+                break;
+            }
+
+            positions.shuffle(rng);
+            let mut puzzle = solution.clone();
+            for &coordinates in &positions {
+                let Some(Cell::Value(value)) = puzzle.get_cell(coordinates) else {
+                    continue;
+                };
+                puzzle.set_cell(coordinates, 0).unwrap();
+                if count_solutions(&puzzle, 2) != 1 {
+                    puzzle.set_cell(coordinates, value).unwrap();
+                }
+            }
+
+            if seen.insert(puzzle.clone()) {
+                puzzles.push(puzzle);
+            }
+        }
+
+        puzzles
+    }
+
+    pub fn generate(clues: usize, rng: &mut impl Rng) -> Option<Board> {
+        Board::generate_avoiding(clues, &[], rng)
+    }
+
+    /// Returns `None` when none of the generated puzzles rates as `target`.
+    pub fn generate_difficulty(target: Difficulty, rng: &mut impl Rng) -> Option<Board> {
+        let clues = match target {
+            Difficulty::Easy => 40,
+            Difficulty::Medium => 30,
+            Difficulty::Hard => 0,
+        };
+        (0..GENERATE_ATTEMPTS).find_map(|_| {
+            let mut puzzle = Board::generate(clues, rng)?;
+            (Sudoku::new(&mut puzzle).difficulty() == target).then_some(puzzle)
+        })
+    }
+
+    /// Returns `None` when no filled grid tried stays unique with the
+    /// forbidden cells cleared.
+    pub fn generate_avoiding(
+        clues: usize,
+        forbidden: &[(Row, Column)],
+        rng: &mut impl Rng,
+    ) -> Option<Board> {
+        for _ in 0..GENERATE_ATTEMPTS {
+            let mut puzzle = Board::new();
+            solve_shuffled(&mut puzzle, rng);
+            for &coordinates in forbidden {
+                puzzle.set_cell(coordinates, 0).unwrap();
+            }
+            if count_solutions(&puzzle, 2) != 1 {
+                continue;
+            }
+
+            let mut positions: Vec<(Row, Column)> = coordinates()
+                .filter(|&coordinates| puzzle.get_cell(coordinates) != Some(Cell::Empty))
+                .collect();
+            positions.shuffle(rng);
+            for coordinates in positions {
+                if puzzle.clue_count() <= clues {
+                    break;
+                }
+                let Some(Cell::Value(value)) = puzzle.get_cell(coordinates) else {
+                    continue;
+                };
+                puzzle.set_cell(coordinates, 0).unwrap();
+                if count_solutions(&puzzle, 2) != 1 {
+                    puzzle.set_cell(coordinates, value).unwrap();
+                }
+            }
+            return Some(puzzle);
+        }
+        None
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl Board {
+    pub fn count_solutions_parallel(&self, limit: usize) -> usize {
+        use rayon::prelude::*;
+
+        if !self.is_valid() {
+            return 0;
+        }
+
+        let Some(coordinate) = self.empty_cells().next() else {
+            return limit.min(1);
+        };
+
+        let count: usize = self
+            .candidates(coordinate)
+            .into_par_iter()
+            .map(|value| {
+                let mut board = self.clone();
+                board.set_cell(coordinate, value).unwrap();
+                count_solutions(&board, limit)
+            })
+            .sum();
+        count.min(limit)
+    }
+}
+
+fn most_constrained<C>(board: &Board, candidates: &C) -> Option<((Row, Column), CandidateSet)>
+where
+    C: Fn(&Board, (Row, Column)) -> CandidateSet,
+{
+    let mut best: Option<((Row, Column), CandidateSet)> = None;
+    for coordinates in board.empty_cells() {
+        let values = candidates(board, coordinates);
+        if best
+            .as_ref()
+            .is_none_or(|(_, best)| values.len() < best.len())
+        {
+            let forced = values.len() <= 1;
+            best = Some((coordinates, values));
+            if forced {
+                break;
+            }
+        }
+    }
+    best
+}
+
+fn solve_counting(board: &mut Board, stats: &mut SolveStats) -> bool {
+    let Some((coordinates, values)) = most_constrained(board, &Board::candidate_set) else {
+        return true;
+    };
+
+    for value in values.iter() {
+        board.set_cell(coordinates, value).unwrap();
+        stats.nodes += 1;
+        if solve_counting(board, stats) {
+            return true;
+        }
+        stats.backtracks += 1;
+    }
+
+    board.set_cell(coordinates, 0).unwrap();
+    false
+}
+
+fn solve_shuffled(board: &mut Board, rng: &mut impl Rng) -> bool {
+    let Some((coordinates, values)) = most_constrained(board, &Board::candidate_set) else {
+        return true;
+    };
+
+    let mut values: Vec<u8> = values.iter().collect();
+    values.shuffle(rng);
+    for value in values {
+        board.set_cell(coordinates, value).unwrap();
+        if solve_shuffled(board, rng) {
+            return true;
+        }
+    }
+
+    board.set_cell(coordinates, 0).unwrap();
+    false
+}
+
+fn solve_until(board: &mut Board, deadline: Instant) -> Option<bool> {
+    let Some((coordinates, values)) = most_constrained(board, &Board::candidate_set) else {
+        return Some(true);
+    };
+
+    for value in values.iter() {
+        if Instant::now() >= deadline {
+            return None;
+        }
+        board.set_cell(coordinates, value).unwrap();
+        if solve_until(board, deadline)? {
+            return Some(true);
+        }
+    }
+
+    board.set_cell(coordinates, 0).unwrap();
+    Some(false)
+}
+
+fn search<C, F>(board: &mut Board, candidates: &C, f: &mut F) -> ControlFlow<()>
+where
+    C: Fn(&Board, (Row, Column)) -> CandidateSet,
+    F: FnMut(&Board) -> ControlFlow<()>,
+{
+    let Some((coordinates, values)) = most_constrained(board, candidates) else {
+        return f(board);
+    };
+
+    for value in values.iter() {
+        board.set_cell(coordinates, value).unwrap();
+        search(board, candidates, f)?;
+    }
+
+    board.set_cell(coordinates, 0).unwrap();
+    ControlFlow::Continue(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn backtracking_solve() {
+        let mut board = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let solution = Sudoku::new(&mut board).solve().unwrap();
+
+        assert!(solution.all_rows_completed());
+        assert!(solution.all_columns_completed());
+        assert!(solution.all_squares_completed());
+        assert!(board.all_squares_completed());
+
+        let mut unsolvable = Board::try_from(
+            "023456789000000000000000000100000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        assert!(Sudoku::new(&mut unsolvable).solve().is_none());
+    }
+
+    #[test]
+    fn repeated_solves_stay_correct() {
+        let puzzle = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        for _ in 0..50 {
+            let mut board = puzzle.clone();
+            let solution = Sudoku::new(&mut board).solve().unwrap();
+            assert!(solution.validate_complete().is_ok());
+            assert!(solution.is_valid_solution_of(&puzzle));
+        }
+    }
+
+    #[test]
+    fn naked_pairs_log_their_eliminations() {
+        let mut candidates = CandidateGrid::new();
+        let marks: [((Row, Column), &[u8]); 5] = [
+            ((Row::A, Column::One), &[1, 2]),
+            ((Row::A, Column::Two), &[1, 2]),
+            ((Row::A, Column::Three), &[1, 2, 3]),
+            ((Row::A, Column::Four), &[2, 5]),
+            ((Row::A, Column::Five), &[4]),
+        ];
+        for (coordinates, digits) in marks {
+            for &digit in digits {
+                candidates.toggle(coordinates, digit).unwrap();
+            }
+        }
+
+        let mut board = Board::new();
+        let mut log = Vec::new();
+        let eliminated = Sudoku::new(&mut board).apply_naked_pairs(&mut candidates, &mut log);
+
+        let reason = String::from("naked pair 12 at A1 and A2");
+        let elimination = |coordinate, digit| Elimination {
+            coordinate,
+            digit,
+            reason: reason.clone(),
+        };
+        assert_eq!(eliminated, 3);
+        assert_eq!(
+            log,
+            vec![
+                elimination((Row::A, Column::Three), 1),
+                elimination((Row::A, Column::Three), 2),
+                elimination((Row::A, Column::Four), 2),
+            ]
+        );
+        assert_eq!(
+            candidates.get((Row::A, Column::One)),
+            CandidateSet::from_iter([1, 2])
+        );
+        assert_eq!(
+            candidates.get((Row::A, Column::Four)),
+            CandidateSet::from_iter([5])
+        );
+    }
+
+    #[test]
+    fn coloring_removes_digit_seeing_both_colors() {
+        let mut candidates = CandidateGrid::new();
+        let chain = [
+            (Row::A, Column::One),
+            (Row::A, Column::Five),
+            (Row::D, Column::Five),
+            (Row::D, Column::Two),
+        ];
+        let others = [
+            (Row::C, Column::Two),
+            (Row::H, Column::Two),
+            (Row::B, Column::Three),
+        ];
+        for coordinates in chain.into_iter().chain(others) {
+            candidates.toggle(coordinates, 1).unwrap();
+        }
+
+        let mut board = Board::new();
+        let mut log = Vec::new();
+        let eliminated = Sudoku::new(&mut board).apply_coloring(&mut candidates, 1, &mut log);
+        assert_eq!(eliminated, 1);
+        assert_eq!(
+            log,
+            vec![Elimination {
+                coordinate: (Row::C, Column::Two),
+                digit: 1,
+                reason: String::from("sees both colors of the 1 chain through A1"),
+            }]
+        );
+        assert!(!candidates.get((Row::C, Column::Two)).contains(1));
+        assert!(candidates.get((Row::H, Column::Two)).contains(1));
+        assert!(candidates.get((Row::B, Column::Three)).contains(1));
+        assert!(chain
+            .iter()
+            .all(|&coordinates| candidates.get(coordinates).contains(1)));
+    }
+
+    #[test]
+    fn repeated_solves_finish_in_time() {
+        let puzzle = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let start = Instant::now();
+        for _ in 0..200 {
+            let mut board = puzzle.clone();
+            let solution = first_solution(&board, &Board::candidate_set).unwrap();
+            assert!(solution.is_valid_solution_of(&puzzle));
+            assert!(Sudoku::new(&mut board).solve().is_some());
+        }
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    fn replay(puzzle: &Board, steps: &[SolveStep]) -> Board {
+        let mut board = puzzle.clone();
+        for step in steps {
+            let (SolveStep::NakedSingle { coordinate, value }
+            | SolveStep::HiddenSingle {
+                coordinate, value, ..
+            }
+            | SolveStep::Guess { coordinate, value }) = *step;
+            assert!(board.candidates(coordinate).contains(&value));
+            board.set_cell(coordinate, value).unwrap();
+        }
+        board
+    }
+
+    #[test]
+    fn trace_of_singles_puzzle_has_no_guesses() {
+        let mut puzzle = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let (solution, steps) = Sudoku::new(&mut puzzle).solve_with_trace().unwrap();
+
+        assert_eq!(steps.len(), 81 - puzzle.clue_count());
+        assert!(!steps
+            .iter()
+            .any(|step| matches!(step, SolveStep::Guess { .. })));
+        assert_eq!(replay(&puzzle, &steps), solution);
+        assert!(solution.validate_complete().is_ok());
+    }
+
+    #[test]
+    fn trace_with_guesses_replays_to_solution() {
+        let mut puzzle = Board::try_from(
+            "800000000003600000070090200050007000000045700000100030001000068008500010090000400",
+        )
+        .unwrap();
+        let (solution, steps) = Sudoku::new(&mut puzzle).solve_with_trace().unwrap();
+
+        assert!(steps
+            .iter()
+            .any(|step| matches!(step, SolveStep::Guess { .. })));
+        assert_eq!(replay(&puzzle, &steps), solution);
+        assert!(solution.is_valid_solution_of(&puzzle));
+
+        let mut unsolvable = Board::try_from(
+            "023456789000000000000000000100000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        assert!(Sudoku::new(&mut unsolvable).solve_with_trace().is_none());
+    }
+
+    #[test]
+    fn count_solutions_up_to_cap() {
+        let mut unique = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        assert_eq!(Sudoku::new(&mut unique).solution_count(2), 1);
+
+        let mut empty = Board::new();
+        let sudoku = Sudoku::new(&mut empty);
+        assert_eq!(sudoku.solution_count(2), 2);
+        assert_eq!(sudoku.solution_count(0), 0);
+    }
+
+    #[test]
+    fn solution_iter_yields_lazily() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719620048864910072235748916";
+        let mut board = Board::try_from(input).unwrap();
+        let sudoku = Sudoku::new(&mut board);
+
+        let solutions: Vec<Board> = sudoku.solution_iter().take(2).collect();
+
+        assert_eq!(solutions.len(), 2);
+        assert_ne!(solutions[0], solutions[1]);
+        assert!(solutions
+            .iter()
+            .all(|solution| solution.validate_complete().is_ok()));
+        assert_eq!(
+            Sudoku::new(&mut Board::new())
+                .solution_iter()
+                .take(3)
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn forced_cells_agree_across_solutions() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719620048864910072235748916";
+        let mut board = Board::try_from(input).unwrap();
+        let forced = Sudoku::new(&mut board).forced_cells(10);
+
+        let open = [(6, 5), (6, 6), (7, 5), (7, 6)];
+        for (row, column) in (0..81).map(|index| (index / 9, index % 9)) {
+            if open.contains(&(row, column)) {
+                assert_eq!(forced[row][column], None);
+            } else {
+                assert!(forced[row][column].is_some());
+            }
+        }
+        assert_eq!(forced[0][0], Some(1));
+    }
+
+    #[test]
+    fn for_each_solution_visits_every_solution() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719620048864910072235748916";
+        let mut board = Board::try_from(input).unwrap();
+        let sudoku = Sudoku::new(&mut board);
+
+        let mut count = 0;
+        sudoku.for_each_solution(|solution| {
+            assert!(solution.all_rows_completed());
+            assert!(solution.all_columns_completed());
+            count += 1;
+            ControlFlow::Continue(())
+        });
+
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn for_each_solution_stops_on_break() {
+        let mut board = Board::new();
+        let sudoku = Sudoku::new(&mut board);
+
+        let mut count = 0;
+        sudoku.for_each_solution(|_| {
+            count += 1;
+            ControlFlow::Break(())
+        });
+
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn for_each_solution_skips_invalid_boards() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748911";
+        let mut board = Board::try_from(input).unwrap();
+        let sudoku = Sudoku::new(&mut board);
+
+        let mut count = 0;
+        sudoku.for_each_solution(|_| {
+            count += 1;
+            ControlFlow::Continue(())
+        });
+
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn implications_of_placement() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291719620048864910072235748916";
+        let mut board = Board::try_from(input).unwrap();
+        let sudoku = Sudoku::new(&mut board);
+
+        assert_eq!(
+            sudoku.implications((Row::G, Column::Six), 3),
+            vec![
+                ((Row::G, Column::Seven), 5),
+                ((Row::H, Column::Six), 5),
+                ((Row::H, Column::Seven), 3),
+            ]
+        );
+        assert_eq!(
+            sudoku.sudoku.get_cell((Row::G, Column::Six)),
+            Some(Cell::Empty)
+        );
+        assert!(sudoku.implications((Row::G, Column::Six), 1).is_empty());
+    }
+
+    #[test]
+    fn unsafe_candidate_empties_peer() {
+        let mut board = Board::try_from(
+            "023456780000000000000000000000000000000000000000000000000000000000000000000000009",
+        )
+        .unwrap();
+        let sudoku = Sudoku::new(&mut board);
+
+        assert_eq!(
+            sudoku.safe_candidates((Row::A, Column::One)),
+            CandidateSet::from_iter([9])
+        );
+        assert_eq!(
+            sudoku.safe_candidates((Row::A, Column::Nine)),
+            CandidateSet::from_iter([1])
+        );
+        assert!(sudoku.safe_candidates((Row::I, Column::Nine)).is_empty());
+    }
+
+    #[test]
+    fn easy_puzzle_uses_only_singles() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let mut board = Board::try_from(input).unwrap();
+        let sudoku = Sudoku::new(&mut board);
+
+        let usage = sudoku.technique_usage();
+        let empty = input.chars().filter(|&c| c == '0').count();
+
+        assert_eq!(usage.get(&Strategy::Guess), None);
+        assert_eq!(
+            usage.get(&Strategy::NakedSingle).unwrap_or(&0)
+                + usage.get(&Strategy::HiddenSingle).unwrap_or(&0),
+            empty
+        );
+    }
+
+    #[test]
+    fn derived_puzzles_are_minimal_and_distinct() {
+        let solution = Board::try_from(
+            "483921657967345821251876493548132976729564138136798245372689514814253769695417382",
+        )
+        .unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        let puzzles = Board::derive_puzzles(&solution, 2, &mut rng);
+
+        assert_eq!(puzzles.len(), 2);
+        assert_ne!(puzzles[0].to_bytes(), puzzles[1].to_bytes());
+        for puzzle in puzzles {
+            assert_eq!(count_solutions(&puzzle, 2), 1);
+            let solved = first_solution(&puzzle, &Board::candidate_set).unwrap();
+            assert_eq!(solved, solution);
+        }
+    }
+
+    #[test]
+    fn scrambled_puzzle_keeps_unique_solution() {
+        let puzzle = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let mut solution = puzzle.clone();
+        Sudoku::new(&mut solution).solve_strict().unwrap();
+
+        let mut scrambled = puzzle.scramble(&mut StdRng::seed_from_u64(5));
+        assert_ne!(scrambled, puzzle);
+        assert!(scrambled.is_equivalent(&puzzle));
+
+        let clues = scrambled.clone();
+        Sudoku::new(&mut scrambled).solve_strict().unwrap();
+        assert!(scrambled.is_valid_solution_of(&clues));
+        assert!(scrambled.is_equivalent(&solution));
+    }
+
+    #[test]
+    fn randomized_solve_depends_on_seed() {
+        let solve = |seed| {
+            let mut board = Board::new();
+            assert!(Sudoku::new(&mut board).solve_randomized(&mut StdRng::seed_from_u64(seed)));
+            assert!(board.validate_complete().is_ok());
+            board.to_string()
+        };
+
+        assert_eq!(solve(1), solve(1));
+        assert_ne!(solve(1), solve(2));
+    }
+
+    #[test]
+    fn deterministic_solve_picks_smallest_solution() {
+        let mut board = Board::try_from(
+            "123456789578139624496872153952381467641297835387564291719620048864910072235748916",
+        )
+        .unwrap();
+        assert!(Sudoku::new(&mut board).solve_deterministic());
+        assert_eq!(board.get_cell((Row::G, Column::Six)), Some(Cell::Value(3)));
+
+        let mut empty = Board::new();
+        assert!(Sudoku::new(&mut empty).solve_deterministic());
+        assert_eq!(
+            empty.to_string().replace('\n', ""),
+            "123456789456789123789123456214365897365897214897214365531642978642978531978531642"
+        );
+    }
+
+    #[test]
+    fn strict_solve_requires_unique_solution() {
+        let mut unique = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        assert_eq!(Sudoku::new(&mut unique).solve_strict(), Ok(()));
+        assert!(unique.validate_complete().is_ok());
+
+        let input =
+            "123456789578139624496872153952381467641297835387564291719620048864910072235748916";
+        let mut ambiguous = Board::try_from(input).unwrap();
+        assert_eq!(
+            Sudoku::new(&mut ambiguous).solve_strict(),
+            Err(SolveError::MultipleSolutions)
+        );
+        assert_eq!(
+            ambiguous.to_string(),
+            Board::try_from(input).unwrap().to_string()
+        );
+
+        let input =
+            "023456789000000000000000000100000000000000000000000000000000000000000000000000000";
+        let mut unsolvable = Board::try_from(input).unwrap();
+        assert_eq!(
+            Sudoku::new(&mut unsolvable).solve_strict(),
+            Err(SolveError::NoSolution)
+        );
+        assert_eq!(
+            unsolvable.to_string(),
+            Board::try_from(input).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn reject_fewer_than_seventeen_clues() {
+        let solution =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        let clues = |count| {
+            let input: String = solution
+                .chars()
+                .enumerate()
+                .map(|(index, c)| if index < count { c } else { '0' })
+                .collect();
+            Board::try_from(input.as_str()).unwrap()
+        };
+
+        let mut sparse = clues(16);
+        assert_eq!(sparse.clue_count(), 16);
+        assert_eq!(
+            Sudoku::new(&mut sparse).validate_clue_count(),
+            Err(SolveError::TooFewClues(16))
+        );
+
+        let mut enough = clues(17);
+        assert_eq!(Sudoku::new(&mut enough).validate_clue_count(), Ok(()));
+    }
+
+    #[test]
+    fn generated_puzzle_is_valid_and_unique() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut puzzle = Board::generate(30, &mut rng).unwrap();
+        assert!(puzzle.is_valid());
+        assert!(puzzle.clue_count() >= 30);
+        assert_eq!(count_solutions(&puzzle, 2), 1);
+
+        Sudoku::new(&mut puzzle).solve().unwrap();
+        assert!(puzzle.validate_complete().is_ok());
+
+        let minimal = Board::generate(0, &mut rng).unwrap();
+        assert!(minimal.clue_count() >= 17);
+        assert_eq!(count_solutions(&minimal, 2), 1);
+    }
+
+    #[test]
+    fn generated_puzzle_matches_target_difficulty() {
+        let mut rng = StdRng::seed_from_u64(5);
+
+        for target in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            let mut puzzle = Board::generate_difficulty(target, &mut rng).unwrap();
+            assert_eq!(count_solutions(&puzzle, 2), 1);
+            assert_eq!(Sudoku::new(&mut puzzle).difficulty(), target);
+        }
+    }
+
+    #[test]
+    fn generated_puzzle_avoids_forbidden_cells() {
+        let forbidden: Vec<(Row, Column)> = Board::unit_coordinates(Unit::Square(4)).to_vec();
+        let mut rng = StdRng::seed_from_u64(11);
+        let puzzle = Board::generate_avoiding(30, &forbidden, &mut rng).unwrap();
+
+        assert!(forbidden
+            .iter()
+            .all(|&coordinates| puzzle.get_cell(coordinates) == Some(Cell::Empty)));
+        assert!(puzzle.clue_count() >= 30);
+        assert_eq!(count_solutions(&puzzle, 2), 1);
+    }
+
+    #[test]
+    fn generation_gives_up_when_forbidden_cells_break_uniqueness() {
+        let forbidden: Vec<(Row, Column)> = coordinates().collect();
+        let mut rng = StdRng::seed_from_u64(11);
+
+        assert_eq!(Board::generate_avoiding(30, &forbidden, &mut rng), None);
+    }
+
+    #[test]
+    fn solve_target_unit_first() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let mut board = Board::try_from(input).unwrap();
+
+        assert!(Sudoku::new(&mut board).solve_unit_first(Unit::Column(Column::Five)));
+        assert!(board.is_column_completed(Column::Five));
+        assert!(board.validate_complete().is_ok());
+
+        let mut unsolvable = Board::try_from(
+            "023456789000000000000000000100000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        assert!(!Sudoku::new(&mut unsolvable).solve_unit_first(Unit::Row(Row::A)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn puzzle_record_json_round_trip() {
+        let puzzle = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let record = PuzzleRecord::from_puzzle(puzzle).unwrap();
+        let json = serde_json::to_string(&record).unwrap();
+        let decoded: PuzzleRecord = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(decoded.puzzle, record.puzzle);
+        assert_eq!(decoded.solution, record.solution);
+        assert_eq!(decoded.difficulty, record.difficulty);
+        assert_eq!(decoded.clue_count, 32);
+    }
+
+    #[test]
+    fn puzzle_record_metadata() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let record = PuzzleRecord::from_puzzle(Board::try_from(input).unwrap()).unwrap();
+
+        assert_eq!(record.difficulty, Difficulty::Easy);
+        assert_eq!(
+            record.clue_count,
+            input.chars().filter(|&c| c != '0').count()
+        );
+        assert_eq!(
+            record.puzzle.to_string(),
+            Board::try_from(input).unwrap().to_string()
+        );
+        assert!(record.solution.is_valid_solution_of(&record.puzzle));
+
+        let hard = Board::try_from(
+            "800000000003600000070090200050007000000045700000100030001000068008500010090000400",
+        )
+        .unwrap();
+        assert_eq!(
+            PuzzleRecord::from_puzzle(hard).unwrap().difficulty,
+            Difficulty::Hard
+        );
+        assert_eq!(
+            PuzzleRecord::from_puzzle(Board::new()).unwrap_err(),
+            SolveError::MultipleSolutions
+        );
+    }
+
+    #[test]
+    fn easy_puzzle_solves_logically() {
+        let mut board = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let outcome = Sudoku::new(&mut board).solve_logically();
+
+        assert!(matches!(outcome, LogicalResult::SolvedLogically));
+        assert!(board.validate_complete().is_ok());
+    }
+
+    #[test]
+    fn hard_puzzle_requires_guessing() {
+        let input =
+            "800000000003600000070090200050007000000045700000100030001000068008500010090000400";
+        let mut board = Board::try_from(input).unwrap();
+        let outcome = Sudoku::new(&mut board).solve_logically();
+
+        let LogicalResult::RequiredGuessing(stuck) = outcome else {
+            panic!("expected guessing, got {:?}", outcome);
+        };
+        assert!(stuck.iter().any(|cell| cell == Cell::Empty));
+        assert!(board.validate_complete().is_ok());
+
+        let mut unsolvable = Board::try_from(
+            "023456789000000000000000000100000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        assert!(matches!(
+            Sudoku::new(&mut unsolvable).solve_logically(),
+            LogicalResult::Unsolvable
+        ));
+    }
+
+    #[test]
+    fn list_filled_naked_singles() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let original = Board::try_from(input).unwrap();
+        let mut board = original.clone();
+
+        let placements = Sudoku::new(&mut board).fill_naked_singles_listed();
+        let changed: Vec<(Row, Column)> = coordinates()
+            .filter(|&coordinates| board.get_cell(coordinates) != original.get_cell(coordinates))
+            .collect();
+
+        assert!(!placements.is_empty());
+        assert_eq!(placements.len(), changed.len());
+        for (coordinates, value) in placements {
+            assert!(changed.contains(&coordinates));
+            assert_eq!(board.get_cell(coordinates), Some(Cell::Value(value)));
+        }
+    }
+
+    #[test]
+    fn count_logically_determined_cells() {
+        let mut easy = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let empties = 81 - easy.clue_count();
+        assert_eq!(Sudoku::new(&mut easy).logically_determined_count(), empties);
+
+        let mut hard = Board::try_from(
+            "800000000003600000070090200050007000000045700000100030001000068008500010090000400",
+        )
+        .unwrap();
+        let empties = 81 - hard.clue_count();
+        assert!(Sudoku::new(&mut hard).logically_determined_count() < empties);
+    }
+
+    #[test]
+    fn guess_depth_of_logical_and_hard_puzzles() {
+        let mut easy = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        assert_eq!(Sudoku::new(&mut easy).max_guess_depth(), 0);
+
+        let mut hard = Board::try_from(
+            "800000000003600000070090200050007000000045700000100030001000068008500010090000400",
+        )
+        .unwrap();
+        assert!(Sudoku::new(&mut hard).max_guess_depth() >= 1);
+    }
+
+    #[test]
+    fn snapshots_run_from_input_to_solution() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let mut board = Board::try_from(input).unwrap();
+        let snapshots = Sudoku::new(&mut board).solve_snapshots();
+
+        assert_eq!(
+            snapshots.len(),
+            input.chars().filter(|&c| c == '0').count() + 1
+        );
+        assert_eq!(snapshots[0], board);
+        assert_eq!(
+            snapshots.last().unwrap().to_string().replace('\n', ""),
+            "483921657967345821251876493548132976729564138136798245372689514814253769695417382"
+        );
+        assert!(snapshots
+            .windows(2)
+            .all(|pair| pair[1].clue_count() == pair[0].clue_count() + 1));
+    }
+
+    #[test]
+    fn single_chain_covers_singles_only_puzzle() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let mut board = Board::try_from(input).unwrap();
+        let empty = input.chars().filter(|&c| c == '0').count();
+
+        assert_eq!(Sudoku::new(&mut board).longest_single_chain(), empty);
+        assert_eq!(Sudoku::new(&mut Board::new()).longest_single_chain(), 0);
+    }
+
+    #[test]
+    fn classify_by_sufficient_strategy() {
+        let mut easy = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let sudoku = Sudoku::new(&mut easy);
+        assert!(sudoku.is_solvable_by(Strategy::NakedSingle));
+        assert!(sudoku.is_solvable_by(Strategy::Guess));
+
+        let mut hard = Board::try_from(
+            "800000000003600000070090200050007000000045700000100030001000068008500010090000400",
+        )
+        .unwrap();
+        let sudoku = Sudoku::new(&mut hard);
+        assert!(!sudoku.is_solvable_by(Strategy::NakedSingle));
+        assert!(!sudoku.is_solvable_by(Strategy::HiddenSingle));
+        assert!(sudoku.is_solvable_by(Strategy::Guess));
+    }
+
+    #[test]
+    fn detect_type_one_unique_rectangle() {
+        let mut board = Board::try_from(
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916",
+        )
+        .unwrap();
+        for coordinates in [
+            (Row::A, Column::Two),
+            (Row::B, Column::Two),
+            (Row::B, Column::Three),
+            (Row::B, Column::Eight),
+            (Row::F, Column::Two),
+            (Row::F, Column::Three),
+        ] {
+            board.set_cell(coordinates, 0).unwrap();
+        }
+        let mut candidates = board.candidate_grid();
+        let sudoku = Sudoku::new(&mut board);
+
+        assert_eq!(
+            sudoku.find_unique_rectangles(&candidates),
+            vec![[
+                (Row::B, Column::Two),
+                (Row::B, Column::Three),
+                (Row::F, Column::Two),
+                (Row::F, Column::Three),
+            ]]
+        );
+
+        let pair: Vec<u8> = candidates.get((Row::F, Column::Three)).iter().collect();
+        candidates.toggle((Row::F, Column::Three), pair[0]).unwrap();
+        assert!(sudoku.find_unique_rectangles(&candidates).is_empty());
+        assert!(Sudoku::new(&mut Board::new())
+            .find_unique_rectangles(&Board::new().candidate_grid())
+            .is_empty());
+    }
+
+    #[test]
+    fn easiest_next_prefers_naked_singles() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let mut board = Board::try_from(input).unwrap();
+        let sudoku = Sudoku::new(&mut board);
+
+        let hint = sudoku.easiest_next().unwrap();
+        let expected = naked_single(sudoku.sudoku).unwrap();
+
+        assert_eq!(hint.strategy, Strategy::NakedSingle);
+        assert_eq!((hint.coordinate, hint.value), expected);
+        assert!(hint.reason.contains("only candidate"));
+        assert_eq!(
+            board.to_string(),
+            Board::try_from(input).unwrap().to_string()
+        );
+
+        let mut board = Board::new();
+        board.set_cell((Row::B, Column::Four), 1).unwrap();
+        board.set_cell((Row::C, Column::Seven), 1).unwrap();
+        board.set_cell((Row::D, Column::Two), 1).unwrap();
+        board.set_cell((Row::G, Column::Three), 1).unwrap();
+        let hint = Sudoku::new(&mut board).easiest_next().unwrap();
+
+        assert_eq!(hint.strategy, Strategy::HiddenSingle);
+        assert_eq!((hint.coordinate, hint.value), ((Row::A, Column::One), 1));
+        assert_eq!(hint.reason, "A1 is the only place for 1 in box 1");
+    }
+
+    #[test]
+    fn logical_solve_within_step_limit() {
+        let mut short = Board::try_from(
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916",
+        )
+        .unwrap();
+        for coordinates in [
+            (Row::A, Column::One),
+            (Row::E, Column::Five),
+            (Row::I, Column::Nine),
+        ] {
+            short.set_cell(coordinates, 0).unwrap();
+        }
+        let sudoku = Sudoku::new(&mut short);
+        assert!(sudoku.is_logically_solvable_within(3));
+        assert!(!sudoku.is_logically_solvable_within(2));
+
+        let mut long = Board::try_from(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        let sudoku = Sudoku::new(&mut long);
+        assert!(!sudoku.is_logically_solvable_within(10));
+        assert!(sudoku.is_logically_solvable_within(81));
+    }
+
+    #[test]
+    fn empty_board_requires_guessing() {
+        let mut board = Board::new();
+        let sudoku = Sudoku::new(&mut board);
+
+        assert_eq!(sudoku.technique_usage().get(&Strategy::Guess), Some(&81));
+    }
+
+    #[test]
+    fn solve_with_jigsaw_regions() {
+        let regions = Regions::new([
+            [2, 2, 2, 2, 8, 8, 6, 6, 6],
+            [5, 2, 2, 2, 8, 8, 1, 6, 6],
+            [5, 2, 2, 8, 8, 8, 1, 1, 6],
+            [5, 5, 8, 8, 0, 0, 1, 1, 6],
+            [5, 5, 5, 0, 0, 0, 1, 1, 6],
+            [5, 7, 5, 3, 0, 0, 1, 1, 6],
+            [7, 7, 7, 3, 0, 4, 4, 4, 4],
+            [7, 7, 7, 3, 0, 4, 4, 4, 4],
+            [7, 7, 3, 3, 3, 3, 3, 3, 4],
+        ])
+        .unwrap();
+        let input =
+            "405007000067000840100060000008341500049053017002000031030000000000000700050734280";
+        let expected =
+            "495817362367129845123465978678341529849253617582976431736582194214698753951734286";
+        let mut board = Board::try_from(input).unwrap();
+        let sudoku = Sudoku::new(&mut board);
+        let solution = sudoku.solve_jigsaw(&regions).unwrap();
+
+        assert_eq!(
+            solution.to_string(),
+            Board::try_from(expected).unwrap().to_string()
+        );
+        assert!((0..9).all(|region| solution.is_region_completed(&regions, region)));
+        assert!(!(0..9).all(|region| solution.is_region_completed(&Regions::standard(), region)));
+    }
+
+    #[test]
+    fn jigsaw_solve_rejects_conflicting_givens() {
+        let regions = Regions::new([
+            [2, 2, 2, 2, 8, 8, 6, 6, 6],
+            [5, 2, 2, 2, 8, 8, 1, 6, 6],
+            [5, 2, 2, 8, 8, 8, 1, 1, 6],
+            [5, 5, 8, 8, 0, 0, 1, 1, 6],
+            [5, 5, 5, 0, 0, 0, 1, 1, 6],
+            [5, 7, 5, 3, 0, 0, 1, 1, 6],
+            [7, 7, 7, 3, 0, 4, 4, 4, 4],
+            [7, 7, 7, 3, 0, 4, 4, 4, 4],
+            [7, 7, 3, 3, 3, 3, 3, 3, 4],
+        ])
+        .unwrap();
+
+        let mut region = Board::new();
+        region.set_cell((Row::A, Column::One), 5).unwrap();
+        region.set_cell((Row::B, Column::Four), 5).unwrap();
+        assert!(!region.is_valid_jigsaw(&regions));
+        assert!(Sudoku::new(&mut region).solve_jigsaw(&regions).is_none());
+
+        let mut column = Board::new();
+        column.set_cell((Row::A, Column::One), 5).unwrap();
+        column.set_cell((Row::I, Column::One), 5).unwrap();
+        assert!(Sudoku::new(&mut column).solve_jigsaw(&regions).is_none());
+
+        let mut box_only = Board::new();
+        box_only.set_cell((Row::A, Column::Six), 5).unwrap();
+        box_only.set_cell((Row::B, Column::Four), 5).unwrap();
+        assert!(!box_only.is_valid());
+        assert!(box_only.is_valid_jigsaw(&regions));
+    }
+
+    #[test]
+    fn solve_x_sudoku() {
+        let input =
+            "200076900600000104900000000000503801060200500005701200030600008040008392000309050";
+        let expected =
+            "214876935678935124953412687427593861361284579895761243539627418746158392182349756";
+        let mut board = Board::try_from(input).unwrap();
+        let sudoku = Sudoku::new(&mut board);
+
+        let mut standard_solutions = 0;
+        sudoku.for_each_solution(|_| {
+            standard_solutions += 1;
+            ControlFlow::Continue(())
+        });
+        assert!(standard_solutions > 1);
+
+        let solution = sudoku.solve_variant(Variant::XSudoku).unwrap();
+        assert_eq!(
+            solution.to_string(),
+            Board::try_from(expected).unwrap().to_string()
+        );
+        assert!(solution.all_diagonals_completed());
+        assert!(solution.is_valid_for(Variant::XSudoku));
+    }
+
+    #[test]
+    fn variant_solve_rejects_conflicting_givens() {
+        let mut diagonal = Board::new();
+        diagonal.set_cell((Row::A, Column::One), 5).unwrap();
+        diagonal.set_cell((Row::E, Column::Five), 5).unwrap();
+        let sudoku = Sudoku::new(&mut diagonal);
+        assert!(sudoku.solve_variant(Variant::XSudoku).is_none());
+        assert!(sudoku.solve_variant(Variant::Standard).is_some());
+
+        let mut square = Board::new();
+        square.set_cell((Row::A, Column::One), 5).unwrap();
+        square.set_cell((Row::B, Column::Two), 5).unwrap();
+        assert!(Sudoku::new(&mut square)
+            .solve_variant(Variant::Standard)
+            .is_none());
+    }
+
+    #[test]
+    fn solve_with_anti_knight() {
+        let mut board = Board::new();
+        let sudoku = Sudoku::new(&mut board);
+        let solution = sudoku
+            .solve_with_constraints(&[Constraint::AntiKnight])
+            .unwrap();
+
+        assert!(solution.all_rows_completed());
+        assert!(solution.all_columns_completed());
+        assert!(solution.is_valid_with(&[Constraint::AntiKnight]));
+    }
+
+    #[test]
+    fn anti_knight_solve_rejects_conflicting_givens() {
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::Three), 5).unwrap();
+        board.set_cell((Row::B, Column::Five), 5).unwrap();
+        let sudoku = Sudoku::new(&mut board);
+
+        assert!(sudoku
+            .solve_with_constraints(&[Constraint::AntiKnight])
+            .is_none());
+        assert!(sudoku.solve_with_constraints(&[]).is_some());
+    }
+
+    #[test]
+    fn trivially_forced_by_clues() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let mut board = Board::try_from(input).unwrap();
+        let sudoku = Sudoku::new(&mut board);
+
+        assert_eq!(
+            sudoku.trivially_forced(),
+            vec![
+                ((Row::A, Column::Six), 1),
+                ((Row::B, Column::Two), 6),
+                ((Row::B, Column::Seven), 8),
+                ((Row::C, Column::One), 2),
+                ((Row::E, Column::Two), 2),
+                ((Row::E, Column::Three), 9),
+                ((Row::E, Column::Four), 5),
+                ((Row::E, Column::Six), 4),
+                ((Row::E, Column::Seven), 1),
+                ((Row::G, Column::Five), 8),
+                ((Row::H, Column::Five), 5),
+                ((Row::I, Column::One), 6),
+                ((Row::I, Column::Two), 9),
+                ((Row::I, Column::Four), 4),
+                ((Row::I, Column::Eight), 8),
+            ]
+        );
+        assert_eq!(
+            sudoku.sudoku.to_string(),
+            Board::try_from(input).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn easy_puzzle_needs_fewer_nodes() {
+        let easy =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let hard =
+            "800000000003600000070090200050007000000045700000100030001000068008500010090000400";
+
+        let mut board = Board::try_from(easy).unwrap();
+        let easy_stats = Sudoku::new(&mut board).solve_with_stats();
+        assert!(easy_stats.solved);
+        assert_eq!(easy_stats.backtracks, 0);
+        assert!(board.all_rows_completed() && board.all_columns_completed());
+
+        let mut board = Board::try_from(hard).unwrap();
+        let hard_stats = Sudoku::new(&mut board).solve_with_stats();
+        assert!(hard_stats.solved);
+        assert!(hard_stats.backtracks > 0);
+        assert!(hard_stats.nodes > 10 * easy_stats.nodes);
+    }
+
+    #[test]
+    fn unsolvable_puzzle_reports_not_solved() {
+        let input =
+            "023456789000000000000000000100000000000000000000000000000000000000000000000000000";
+        let mut board = Board::try_from(input).unwrap();
+
+        let stats = Sudoku::new(&mut board).solve_with_stats();
+        assert!(!stats.solved);
+        assert_eq!(board.get_cell((Row::A, Column::One)), Some(Cell::Empty));
+
+        let mut board = Board::new();
+        board.set_cell((Row::A, Column::One), 1).unwrap();
+        board.set_cell((Row::A, Column::Two), 1).unwrap();
+        assert!(!Sudoku::new(&mut board).solve_with_stats().solved);
+    }
+
+    #[test]
+    fn solve_with_timeout_outcomes() {
+        let mut board = Board::new();
+        let outcome = Sudoku::new(&mut board).solve_with_timeout(Duration::from_millis(1));
+        assert!(matches!(
+            outcome,
+            SolveOutcome::Solved | SolveOutcome::TimedOut
+        ));
+
+        let mut board = Board::new();
+        let outcome = Sudoku::new(&mut board).solve_with_timeout(Duration::ZERO);
+        assert_eq!(outcome, SolveOutcome::TimedOut);
+        assert!(board.iter().all(|cell| cell == Cell::Empty));
+
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let mut board = Board::try_from(input).unwrap();
+        let outcome = Sudoku::new(&mut board).solve_with_timeout(Duration::from_secs(10));
+        assert_eq!(outcome, SolveOutcome::Solved);
+
+        let input =
+            "023456789000000000000000000100000000000000000000000000000000000000000000000000000";
+        let mut board = Board::try_from(input).unwrap();
+        let outcome = Sudoku::new(&mut board).solve_with_timeout(Duration::from_secs(10));
+        assert_eq!(outcome, SolveOutcome::Unsolvable);
+    }
+
+    #[test]
+    fn redundant_clues_of_non_minimal_puzzle() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let mut board = Board::try_from(input).unwrap();
+        let sudoku = Sudoku::new(&mut board);
+
+        let redundant = sudoku.redundant_clues();
+        assert!(!redundant.is_empty());
+        for coordinates in redundant {
+            let mut board = Board::try_from(input).unwrap();
+            board.set_cell(coordinates, 0).unwrap();
+            assert_eq!(count_solutions(&board, 2), 1);
+        }
+    }
+
+    #[test]
+    fn redundant_pair_of_added_clues() {
+        let input =
+            "020400700070039004006000103900081000000200000307500200010003500000010000005700010";
+        let minimal = Board::try_from(input).unwrap();
+        let solution = first_solution(&minimal, &Board::candidate_set).unwrap();
+        let (first, second) = ((Row::A, Column::One), (Row::I, Column::Nine));
+
+        let mut board = minimal.clone();
+        for coordinates in [first, second] {
+            let Some(Cell::Value(value)) = solution.get_cell(coordinates) else {
+                panic!("solution is incomplete");
+            };
+            board.set_cell(coordinates, value).unwrap();
+        }
+        let sudoku = Sudoku::new(&mut board);
+        let redundant = sudoku.redundant_clues();
+        let pairs = sudoku.redundant_pairs();
+
+        assert!(pairs.contains(&(first, second)));
+        assert!(pairs
+            .iter()
+            .all(|(a, b)| redundant.contains(a) && redundant.contains(b)));
+        assert!(Sudoku::new(&mut minimal.clone())
+            .redundant_pairs()
+            .is_empty());
+    }
+
+    #[test]
+    fn repair_single_typo() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let mut board = Board::try_from(input).unwrap();
+        assert_eq!(Sudoku::new(&mut board).nearest_solvable(), None);
+
+        board.set_cell((Row::A, Column::Three), 6).unwrap();
+        assert!(!board.is_valid());
+        assert_eq!(
+            Sudoku::new(&mut board).nearest_solvable(),
+            Some((Row::A, Column::Three, 3))
+        );
+    }
+
+    #[test]
+    fn minimal_puzzle_has_no_redundant_clues() {
+        let input =
+            "020400700070039004006000103900081000000200000307500200010003500000010000005700010";
+        let mut board = Board::try_from(input).unwrap();
+        let sudoku = Sudoku::new(&mut board);
+
+        assert!(sudoku.redundant_clues().is_empty());
+    }
+
+    #[test]
+    fn solve_killer_with_cages() {
+        let input =
+            "123456789578139624496872153952381467641297835387564291000000000000000000000000000";
+        let expected =
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916";
+        let cage = |cells: &[(usize, usize)], sum| Cage {
+            cells: cells
+                .iter()
+                .map(|&(row, column)| {
+                    (
+                        Row::try_from(row).unwrap(),
+                        Column::try_from(column).unwrap(),
+                    )
+                })
+                .collect(),
+            sum,
+        };
+        let cages = [
+            cage(&[(6, 0), (6, 1), (6, 2)], 17),
+            cage(&[(6, 3), (6, 4), (6, 5)], 11),
+            cage(&[(6, 6), (6, 7), (6, 8)], 17),
+            cage(&[(7, 0), (7, 1), (7, 2)], 18),
+            cage(&[(7, 3), (7, 4), (7, 5)], 15),
+            cage(&[(7, 6), (7, 7), (7, 8)], 12),
+            cage(&[(8, 0), (8, 1)], 5),
+            cage(&[(8, 2), (8, 3)], 12),
+            cage(&[(8, 4), (8, 5)], 12),
+            cage(&[(8, 6), (8, 7), (8, 8)], 16),
+        ];
+        let mut board = Board::try_from(input).unwrap();
+        assert_eq!(count_solutions(&board, 2), 2);
+
+        let sudoku = Sudoku::new(&mut board);
+        let solution = sudoku.solve_killer(&cages).unwrap();
+
+        assert_eq!(
+            solution.to_string(),
+            Board::try_from(expected).unwrap().to_string()
+        );
+        assert!(solution.is_valid_killer(&cages));
+    }
+
+    #[test]
+    fn solve_to_flat_array() {
+        let input =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+        let expected =
+            "483921657967345821251876493548132976729564138136798245372689514814253769695417382";
+        let mut board = Board::try_from(input).unwrap();
+        let sudoku = Sudoku::new(&mut board);
+
+        let digits = sudoku.solve_to_array().unwrap();
+        let expected: Vec<u8> = expected.bytes().map(|b| b - b'0').collect();
+        assert_eq!(digits.to_vec(), expected);
+
+        let input =
+            "023456789000000000000000000100000000000000000000000000000000000000000000000000000";
+        let mut board = Board::try_from(input).unwrap();
+        assert_eq!(Sudoku::new(&mut board).solve_to_array(), None);
+    }
+
+    #[test]
+    fn solve_puzzles_from_file() {
+        let path = std::env::temp_dir().join("sudoku_solve_file_test.txt");
+        fs::write(
+            &path,
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300\n\
+             \n\
+             123456789578139624496872153952381467641297835387564291719623548864915372235748916\n",
+        )
+        .unwrap();
+        let solutions = solve_file(&path).unwrap();
+
+        assert_eq!(solutions.len(), 2);
+        assert!(solutions
+            .iter()
+            .all(|board| board.validate_complete().is_ok()));
+
+        fs::write(
+            &path,
+            "123456789578139624496872153952381467641297835387564291719623548864915372235748916\n\
+             12345\n",
+        )
+        .unwrap();
+        let error = solve_file(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert!(error.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn bounded_batch_reports_timeouts() {
+        let path = std::env::temp_dir().join("sudoku_solve_file_bounded_test.txt");
+        fs::write(
+            &path,
+            "483921657967345821251876493548132976729564138136798245372689514814253769695417382\n\
+             000000000000003085001020000000507000004000100090000000500000073002010000000040009\n",
+        )
+        .unwrap();
+        let report = solve_file_bounded(&path, Duration::ZERO).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(report.solutions.len(), 1);
+        assert_eq!(report.solutions[0].0, 1);
+        assert!(report.solutions[0].1.validate_complete().is_ok());
+        assert_eq!(report.timed_out, vec![2]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_count_matches_sequential() {
+        let input =
+            "000000000000000000000000000952381467641297835387564291719623548864915372235748916";
+        let board = Board::try_from(input).unwrap();
+
+        assert_eq!(
+            board.count_solutions_parallel(1000),
+            count_solutions(&board, 1000)
+        );
+        assert_eq!(board.count_solutions_parallel(2), 2);
+    }
 }